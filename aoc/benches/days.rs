@@ -0,0 +1,54 @@
+//! Times parsing and each part of every day, on the checked-in example and on the personal puzzle input when it is present.
+//!
+//! Benchmarks are named `dayNN/<step>/<input>`, so `cargo bench -p aoc -- day06/part2` runs one of them.
+//! To measure an optimization, save a baseline first and compare against it afterwards:
+//!
+//! ```text
+//! cargo bench -p aoc -- --save-baseline before day06
+//! cargo bench -p aoc -- --baseline before day06
+//! ```
+
+use std::{fs, hint::black_box, path::Path};
+
+use aoc_common::{Answer, Solver};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_day<S: Solver>(c: &mut Criterion, day: u32) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("aoc is inside the workspace");
+    let mut group = c.benchmark_group(format!("day{day:02}"));
+    for name in ["example", "input"] {
+        let Ok(text) = fs::read_to_string(root.join(format!("aoc_{day:02}/{name}.txt"))) else {
+            continue;
+        };
+        let input = S::parse(&text).unwrap_or_else(|e| panic!("Failed to parse day {day} {name}: {e}"));
+        // Full inputs are much slower, so take fewer samples to keep the whole suite reasonably quick.
+        group.sample_size(if name == "input" { 10 } else { 100 });
+        group.bench_function(format!("parse/{name}"), |b| b.iter(|| S::parse(black_box(&text))));
+        for (part, solve) in [("part1", S::part1 as fn(&S::Input) -> Answer), ("part2", S::part2)] {
+            if solve(&input) == Answer::Unsolved {
+                continue;
+            }
+            group.bench_function(format!("{part}/{name}"), |b| b.iter(|| solve(black_box(&input))));
+        }
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<aoc_01::Day01>(c, 1);
+    bench_day::<aoc_02::Day02>(c, 2);
+    bench_day::<aoc_03::Day03>(c, 3);
+    bench_day::<aoc_04::Day04>(c, 4);
+    bench_day::<aoc_05::Day05>(c, 5);
+    bench_day::<aoc_06::Day06>(c, 6);
+    bench_day::<aoc_07::Day07>(c, 7);
+    bench_day::<aoc_08::Day08>(c, 8);
+    bench_day::<aoc_09::Day09>(c, 9);
+    bench_day::<aoc_10::Day10>(c, 10);
+    bench_day::<aoc_11::Day11>(c, 11);
+    bench_day::<aoc_12::Day12>(c, 12);
+    bench_day::<aoc_13::Day13>(c, 13);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
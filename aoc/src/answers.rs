@@ -0,0 +1,76 @@
+//! The registry of known answers, used to catch regressions.
+//!
+//! Each non-empty line that doesn't start with `#` holds a day, a part, the path of an input relative to the workspace root,
+//! and the expected answer, separated by whitespace. The answer is the rest of the line:
+//!
+//! ```text
+//! # day part input answer
+//! 6 2 aoc_06/example.txt 6
+//! ```
+//!
+//! `answers.txt` holds the answers for the checked-in examples. Answers for personal puzzle inputs, which aren't checked in,
+//! go in `answers.local.txt` next to it.
+
+use std::{fs, io, path::{Path, PathBuf}};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: usize,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+/// Parses the contents of an answers file.
+pub fn parse(text: &str) -> Result<Vec<Expected>, String> {
+    let mut r = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: &str| format!("Line {}: {message}: {line:?}", i + 1);
+        let mut rest = line;
+        let day = next_field(&mut rest).and_then(|s| s.parse().ok()).ok_or_else(|| error("Invalid day"))?;
+        let part = next_field(&mut rest).and_then(|s| s.parse().ok()).filter(|p| *p == 1 || *p == 2).ok_or_else(|| error("Invalid part"))?;
+        let input = next_field(&mut rest).ok_or_else(|| error("Missing input"))?.into();
+        let answer = Some(rest.trim()).filter(|s| !s.is_empty()).ok_or_else(|| error("Missing answer"))?.into();
+        r.push(Expected { day, part, input, answer });
+    }
+    Ok(r)
+}
+
+/// Splits off the next whitespace-separated field from the front of `rest`.
+fn next_field<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    let s = rest.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    let (field, tail) = s.split_at(end);
+    *rest = tail;
+    Some(field).filter(|f| !f.is_empty())
+}
+
+/// Reads an answers file, treating a missing file as empty.
+pub fn load(path: &Path) -> io::Result<Vec<Expected>> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse(&text).map_err(|e| io::Error::other(format!("{}: {e}", path.display()))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines() {
+        let text = "# day part input answer\n\n6 2 aoc_06/example.txt 6\n 11 1  aoc_11/input.txt  55312 \n";
+        assert_eq!(Ok(vec![
+            Expected { day: 6, part: 2, input: "aoc_06/example.txt".into(), answer: "6".into() },
+            Expected { day: 11, part: 1, input: "aoc_11/input.txt".into(), answer: "55312".into() },
+        ]), parse(text));
+        assert_eq!(Err("Line 1: Invalid part: \"6 3 x 1\"".into()), parse("6 3 x 1"));
+        assert!(parse("6 1 x").is_err());
+        assert!(parse("six 1 x 1").is_err());
+    }
+}
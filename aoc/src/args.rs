@@ -0,0 +1,66 @@
+use aoc_common::Parts;
+
+pub const USAGE: &str = "Usage: aoc run <day|all> [--part <1|2>] [--input <path>]";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Days {
+    All,
+    One(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub days: Days,
+    pub parts: Parts,
+    pub input: Option<String>,
+}
+
+/// Parses the arguments following the program name.
+pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Run, String> {
+    let mut args = args.iter().map(AsRef::as_ref);
+    match args.next() {
+        Some("run") => (),
+        Some(other) => return Err(format!("Unknown command {other:?}")),
+        None => return Err("Missing command".into()),
+    }
+    let days = match args.next() {
+        Some("all") => Days::All,
+        Some(day) => Days::One(day.parse().map_err(|_| format!("Invalid day {day:?}"))?),
+        None => return Err("Missing day".into()),
+    };
+    let mut run = Run { days, parts: Parts::Both, input: None };
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("Missing value for {flag}"))?;
+        match flag {
+            "--part" => run.parts = match value {
+                "1" => Parts::Part1,
+                "2" => Parts::Part2,
+                _ => return Err(format!("Invalid part {value:?}")),
+            },
+            "--input" => run.input = Some(value.into()),
+            _ => return Err(format!("Unknown option {flag:?}")),
+        }
+    }
+    if run.days == Days::All && run.input.is_some() {
+        return Err("--input requires a single day".into());
+    }
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(Ok(Run { days: Days::One(6), parts: Parts::Part2, input: Some("example.txt".into()) }),
+            parse(&["run", "6", "--part", "2", "--input", "example.txt"]));
+        assert_eq!(Ok(Run { days: Days::All, parts: Parts::Both, input: None }), parse(&["run", "all"]));
+        assert!(parse(&["run", "all", "--input", "x"]).is_err());
+        assert!(parse(&["run", "6", "--part", "3"]).is_err());
+        assert!(parse(&["run", "6", "--part"]).is_err());
+        assert!(parse(&["run", "six"]).is_err());
+        assert!(parse(&["walk", "6"]).is_err());
+        assert!(parse::<&str>(&[]).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use aoc_common::{Parts, Report, Result};
+
+/// Parses the full text of a puzzle input and runs the selected parts on it.
+pub type Run = fn(&str, Parts) -> Result<Report>;
+
+pub struct Day {
+    pub number: u32,
+    pub run: Run,
+}
+
+pub static DAYS: &[Day] = &[
+    Day { number: 1, run: aoc_common::run::<aoc_01::Day01> },
+    Day { number: 2, run: aoc_common::run::<aoc_02::Day02> },
+    Day { number: 3, run: aoc_common::run::<aoc_03::Day03> },
+    Day { number: 4, run: aoc_common::run::<aoc_04::Day04> },
+    Day { number: 5, run: aoc_common::run::<aoc_05::Day05> },
+    Day { number: 6, run: aoc_common::run::<aoc_06::Day06> },
+    Day { number: 7, run: aoc_common::run::<aoc_07::Day07> },
+    Day { number: 8, run: aoc_common::run::<aoc_08::Day08> },
+    Day { number: 9, run: aoc_common::run::<aoc_09::Day09> },
+    Day { number: 10, run: aoc_common::run::<aoc_10::Day10> },
+    Day { number: 11, run: aoc_common::run::<aoc_11::Day11> },
+    Day { number: 12, run: aoc_common::run::<aoc_12::Day12> },
+    Day { number: 13, run: aoc_common::run::<aoc_13::Day13> },
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+impl Day {
+    /// Where the day's own binary looks for its input, relative to the workspace root.
+    pub fn default_input(&self) -> String {
+        format!("aoc_{:02}/input.txt", self.number)
+    }
+}
@@ -0,0 +1,4 @@
+//! The table of every day's solution and the registry of known answers, shared by the `aoc` binary and the regression tests.
+
+pub mod answers;
+pub mod days;
@@ -0,0 +1,69 @@
+//! Runs any day's solution and reports the answers with timings.
+//!
+//! Usage: `aoc run <day|all> [--part <1|2>] [--input <path>]`
+//!
+//! The input defaults to the `input.txt` in the day's crate directory, so run this from the workspace root,
+//! e.g. `cargo run --release -p aoc -- run 6 --part 2`. When running all days, those without an input are skipped.
+
+use std::{fs, path::Path, process, time::Duration};
+
+use aoc_common::{Answer, Error, Parts, Result};
+
+mod args;
+
+use aoc::days::{self, Day};
+use args::{Days, Run};
+
+/// Runs the selected parts of a day on the input at `path`, printing each answer, and returns the time spent solving.
+fn run_day(day: &Day, parts: Parts, path: &str) -> Result<Duration> {
+    let input = fs::read_to_string(path)
+        .map_err(|e| Error::new(format!("Failed to read {path}: {e}")))?;
+    let report = (day.run)(&input, parts).map_err(|e| e.with_file(path))?;
+    println!("Day {} parse: {:.2?}", day.number, report.parse);
+    for (part, answer, elapsed) in &report.parts {
+        match answer {
+            Answer::Unsolved => println!("Day {} part {part}: unsolved", day.number),
+            answer => println!("Day {} part {part}: {answer} ({elapsed:.2?})", day.number),
+        }
+    }
+    Ok(report.total())
+}
+
+fn run(run: Run) -> Result<()> {
+    match run.days {
+        Days::One(number) => {
+            let day = days::find(number).ok_or_else(|| Error::new(format!("No solution for day {number}")))?;
+            let path = run.input.unwrap_or_else(|| day.default_input());
+            run_day(day, run.parts, &path)?;
+        },
+        Days::All => {
+            let mut total = Duration::ZERO;
+            for day in days::DAYS {
+                let path = day.default_input();
+                if !Path::new(&path).exists() {
+                    println!("Day {}: skipped, no input at {path}", day.number);
+                    continue;
+                }
+                total += run_day(day, run.parts, &path)?;
+            }
+            println!("Total: {total:.2?}");
+        },
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match args::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("{}", args::USAGE);
+            process::exit(2);
+        },
+    };
+    if let Err(e) = run(command) {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
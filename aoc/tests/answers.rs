@@ -0,0 +1,62 @@
+//! Runs every day against each input listed in the answers files and reports any answer that changed.
+//!
+//! Inputs that don't exist, usually personal puzzle inputs on another machine, are skipped with a note.
+//! Any other error reading an input fails the test.
+//! Run with `--nocapture` to see the notes.
+
+use std::{collections::BTreeMap, fs, io, path::{Path, PathBuf}};
+
+use aoc::{answers::{self, Expected}, days};
+use aoc_common::Parts;
+
+#[test]
+fn expected_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("aoc is inside the workspace");
+    let mut expected = answers::load(&root.join("answers.txt")).unwrap();
+    expected.extend(answers::load(&root.join("answers.local.txt")).unwrap());
+    assert!(!expected.is_empty(), "No expected answers found");
+
+    // Each input is solved once for all of its expected answers.
+    let mut by_input: BTreeMap<(u32, PathBuf), Vec<&Expected>> = BTreeMap::new();
+    for e in &expected {
+        by_input.entry((e.day, e.input.clone())).or_default().push(e);
+    }
+
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    for ((day, input), expected) in by_input {
+        let path = root.join(&input);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                println!("Skipped day {day} on {}: input not found", input.display());
+                continue;
+            },
+            Err(e) => {
+                failures.push(format!("Day {day} on {}: {e}", input.display()));
+                continue;
+            },
+        };
+        let Some(solver) = days::find(day) else {
+            failures.push(format!("Day {day} on {}: no solution for this day", input.display()));
+            continue;
+        };
+        let report = match (solver.run)(&text, Parts::Both) {
+            Ok(report) => report,
+            Err(e) => {
+                failures.push(format!("Day {day}: {}", e.with_file(&input)));
+                continue;
+            },
+        };
+        for e in expected {
+            let (_, actual, _) = &report.parts[e.part - 1];
+            let actual = actual.to_string();
+            if actual != e.answer {
+                failures.push(format!("Day {day} part {} on {}: expected {}, got {actual}", e.part, input.display(), e.answer));
+            }
+            checked += 1;
+        }
+    }
+    println!("Checked {checked} answers");
+    assert!(failures.is_empty(), "{} wrong answers:\n{}", failures.len(), failures.join("\n"));
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use aoc_common::{Answer, Error, Result, Solver};
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = (Vec<i64>, Vec<i64>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_sorted(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        calculate_diff(&input.0, &input.1).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        calculate_similarity(&input.0, &input.1).into()
+    }
+}
+
+fn read_sorted(input: &str) -> Result<(Vec<i64>, Vec<i64>)> {
+    let (mut left, mut right) = read_data(input)?;
+    left.sort_unstable();
+    right.sort_unstable();
+    Ok((left, right))
+}
+
+fn read_data(input: &str) -> Result<(Vec<i64>, Vec<i64>)> {
+    let mut l = Vec::new();
+    let mut r = Vec::new();
+    for line in input.lines() {
+        let mut line_iter = line.split_whitespace();
+        l.push(parse_i64(input, line, line_iter.next())?);
+        r.push(parse_i64(input, line, line_iter.next())?);
+    }
+    Ok((l, r))
+}
+
+fn calculate_diff(left: &[i64], right: &[i64]) -> i64 {
+    left.iter().zip(right.iter()).map(|(x,y)| dist(*x, *y)).sum()
+}
+
+fn calculate_similarity(left: &[i64], right: &[i64]) -> i64 {
+    let counts = calculate_counts(right);
+    let mut sum = 0;
+    for val in left {
+        sum += val * counts.get(val).unwrap_or(&0);
+    }
+    sum
+}
+
+fn calculate_counts(data: &[i64]) -> HashMap<i64, i64> {
+    let mut r = HashMap::new();
+    for val in data.iter() {
+        *r.entry(*val).or_insert(0) += 1i64;
+    }
+    r
+}
+
+/// Parses one of the integers on `line`, which is `None` if the line ran out.
+fn parse_i64(input: &str, line: &str, val: Option<&str>) -> Result<i64> {
+    let val = val.ok_or_else(|| Error::new("Expected another integer on the line").at_slice(input, &line[line.len()..]))?;
+    val.parse().map_err(|e| Error::new(format!("Invalid integer {val:?}: {e}")).at_slice(input, val))
+}
+
+fn dist(x: i64, y: i64) -> i64 {
+    if x > y { x - y} else { y - x}
+}
@@ -0,0 +1,89 @@
+use aoc_common::{Answer, Error, Result, Solver};
+use Direction::*;
+
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+enum Direction { Inc, Dec }
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_safe(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_safe_flexible(input).into()
+    }
+}
+
+fn count_safe(data: &[Vec<u64>]) -> u64 {
+    data.iter()
+        // .inspect(|x| println!("{x:?}, {}", is_safe(x)))
+        .filter(|x| is_safe(x))
+        .count() as u64
+}
+
+fn count_safe_flexible(data: &[Vec<u64>]) -> u64 {
+    data.iter()
+        // .inspect(|x| println!("{x:?}, {}", is_safe_flexible(x)))
+        .filter(|x| is_safe_flexible(x))
+        .count() as u64
+}
+
+fn is_safe(data: &[u64]) -> bool {
+    if data.len() <= 1 {
+        return true;
+    }
+    let mut base_direction: Option<Direction> = None;
+    for i in 0..(data.len() - 1) {
+        let (direction, d) = diff(data[i], data[i+1]);
+        if !(1..=3).contains(&d) {
+            return false;
+        }
+        if let Some(base_direction) = base_direction {
+            if base_direction != direction {
+                return false;
+            }
+        } else {
+            base_direction = Some(direction);
+        }
+    }
+    true
+}
+
+fn diff(x: u64, y: u64) -> (Direction, u64) {
+    if x > y { (Dec, x - y) } else { (Inc, y - x) }
+}
+
+fn is_safe_flexible(data: &[u64]) -> bool {
+    subsets(data).any(|x| is_safe(&x))
+}
+
+fn subsets(data: &[u64]) -> impl Iterator<Item = Vec<u64>> + '_ {
+    std::iter::once(data.to_vec()).chain((0..data.len()).map(|i| {
+        let mut r = Vec::with_capacity(data.len() - 1);
+        r.extend(&data[0..i]);
+        r.extend(&data[i+1..]);
+        assert_eq!(r.len(), data.len() - 1);
+        r
+    }))
+}
+
+fn read_data(input: &str) -> Result<Vec<Vec<u64>>> {
+    let mut r = Vec::new();
+    for line in input.lines() {
+        let level = line.split_whitespace().map(|val| parse_u64(input, val)).collect::<Result<Vec<_>>>()?;
+        r.push(level);
+    }
+    Ok(r)
+}
+
+fn parse_u64(input: &str, val: &str) -> Result<u64> {
+    val.parse().map_err(|e| Error::new(format!("Invalid level {val:?}: {e}")).at_slice(input, val))
+}
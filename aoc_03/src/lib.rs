@@ -0,0 +1,79 @@
+use std::num::ParseIntError;
+
+use aoc_common::{Answer, Result, Solver};
+use winnow::{ascii::digit1, combinator::{alt, delimited, separated_pair}, prelude::*, token::literal};
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_instructions(input))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        sum_unconditional(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        sum_conditional(input).into()
+    }
+}
+
+pub enum Instruction {
+    Mul(u32, u32),
+    Do(),
+    Dont(),
+}
+
+fn sum_unconditional(instructions: &[Instruction]) -> u64 {
+    instructions.iter().map(|instr| {
+        match instr {
+            Instruction::Mul(x,y) => (x * y) as u64,
+            _ => 0,
+        }
+    })
+    .sum()
+}
+
+fn sum_conditional(instructions: &[Instruction]) -> u64 {
+    let mut enabled = true;
+    let mut sum: u64 = 0;
+    for instr in instructions {
+        match instr {
+            Instruction::Do() => enabled = true,
+            Instruction::Dont() => enabled = false,
+            Instruction::Mul(x, y) => if enabled { sum += (x * y) as u64 },
+        }
+    }
+    sum
+}
+
+fn parse_instructions(input: &str) -> Vec<Instruction> {
+    let mut input = input;
+    let mut result = Vec::new();
+    while !input.is_empty() {
+        match instruction.parse_next(&mut input) {
+            Ok(instr) => result.push(instr),
+            Err(_) => input = &input[1..],
+        }
+    }
+    result
+}
+
+fn instruction(input: &mut &str) -> PResult<Instruction> {
+    alt((
+        delimited(
+            "mul(",
+            separated_pair(digit1, ',', digit1),
+            ")"
+        )
+        .try_map(|(x, y): (&str, &str)| {
+            Ok::<_, ParseIntError>(Instruction::Mul(x.parse()?, y.parse()?))
+        }),
+        literal("do()").map(|_| Instruction::Do()),
+        literal("don't()").map(|_| Instruction::Dont())
+    ))
+    .parse_next(input)
+}
@@ -0,0 +1,246 @@
+use std::fmt::Display;
+
+use aoc_common::{Answer, Error, Result, Solver};
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Board;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_word(input, b"XMAS", false).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_cross(input).into()
+    }
+}
+
+type Token = u8;
+type Dimension = usize;
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    data: Vec<Token>,
+    width: Dimension,
+    height: Dimension,
+}
+
+impl Board {
+    fn get(&self, index: Idx) -> Option<&Token> {
+        let Idx(x, y) = index;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.data.get(x + y * self.width)
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.data.chunks(self.width) {
+            let s = core::str::from_utf8(line).map_err(|_| std::fmt::Error)?;
+            writeln!(f, "{s}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Clone, Copy, Debug)]
+struct Idx(Dimension, Dimension);
+
+impl Idx {
+    fn add(&self, offset: (isize, isize)) -> Option<Self> {
+       Some(Idx(self.0.checked_add_signed(offset.0)?, self.1.checked_add_signed(offset.1)?))
+    }
+}
+
+fn count_word(board: &Board, target: &[Token], debug: bool) -> u32 {
+    let directions = init_directions();
+    let mut count = 0;
+    for y in 0..board.height {
+        for x in 0..board.width {
+            let index = Idx(x,y);
+            for direction in directions.iter() {
+                if check_direction(board, target, index, direction).is_some() {
+                    count += 1;
+                    if debug {println!("index={index:?}, direction={direction:?}");}
+                }
+            }
+        }
+    }
+    count
+}
+
+fn init_directions() -> Vec<(isize, isize)> {
+    let mut directions: Vec<(isize, isize)> = Vec::new();
+    for x in [-1,0,1] {
+        for y in [-1,0,1] {
+            if x != 0 || y != 0 {
+                directions.push((x,y));
+            }
+        }
+    }
+    assert_eq!(8, directions.len());
+    directions
+}
+
+fn check_direction(board: &Board, target: &[Token], start: Idx, direction: &(isize, isize)) -> Option<()> {
+    let mut pos: Option<Idx> = Some(start);
+    for c in target {
+        if *board.get(pos?)? != *c {
+            return None;
+        }
+        pos = pos.unwrap().add(*direction);
+    }
+    Some(())
+}
+
+fn count_cross(board: &Board) -> u32 {
+    let mut count = 0;
+    for y in 0..board.height {
+        for x in 0..board.width {
+            let index = Idx(x,y);
+            if check_cross(board, index).is_some() { count += 1 }
+        }
+    }
+    count
+}
+
+fn check_cross(board: &Board, index: Idx) -> Option<()> {
+    if *board.get(index)? != b'A' {
+        return None;
+    }
+    let ul = *board.get(index.add((-1, -1))?)?;
+    let dr = *board.get(index.add((1, 1))?)?;
+    let ur = *board.get(index.add((1, -1))?)?;
+    let dl = *board.get(index.add((-1, 1))?)?;
+
+    let left_cross = (ul == b'M' && dr == b'S') || (ul == b'S' && dr == b'M');
+    let right_cross = (ur == b'M' && dl == b'S') || (ur == b'S' && dl == b'M');
+    if left_cross && right_cross { Some(()) } else { None }
+}
+
+fn read_data(input: &str) -> Result<Board> {
+    let mut width: Option<usize> = None;
+    let mut height = 0;
+    let mut data = Vec::new();
+    for line in input.lines() {
+        let bytes = line.as_bytes();
+        data.extend(bytes);
+        if let Some(width) = width {
+            if width != bytes.len() {
+                let message = format!("Expected {width} letters on every line, found {}", bytes.len());
+                return Err(Error::new(message).at_slice(input, &line[width.min(bytes.len())..]));
+            }
+        } else {
+            width = Some(bytes.len());
+        }
+        height += 1;
+    }
+    match width {
+        None => Err(Error::new("Empty board")),
+        Some(width) => Ok(Board { data, width, height }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{prelude::*};
+
+    fn ascii_string(length: usize) -> impl Strategy<Value = String> {
+        proptest::string::string_regex(&format!("[a-zA-Z0-9]{{{}}}", length)).unwrap()
+    }
+
+    impl Arbitrary for Board {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Board>;
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            (1..100usize, 1..100usize).prop_flat_map(|(width, height)| {
+                ascii_string(width * height).prop_map(move|data: String| {
+                    Board {data: data.bytes().collect(), width, height}
+                })
+            }).boxed()
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10000))]
+
+        #[test]
+        fn check_indexing(board: Board) {
+            use std::fmt::Write;
+            let display = board.to_string();
+            let mut manual = String::new();
+            for y in 0..board.height {
+                for x in 0..board.width {
+                    write!(manual, "{}", *board.get(Idx(x,y)).unwrap() as char).unwrap();
+                }
+                writeln!(manual).unwrap();
+            }
+            assert_eq!(display, manual);
+        }
+
+        #[test]
+        fn check_count(board: Board, target in ascii_string(3)) {
+            let forward_count = count_word(&board, target.as_bytes(), false);
+            let mut backwards = Vec::new();
+            backwards.extend(target.as_bytes().iter().rev());
+            let backward_count = count_word(&board, &backwards, false);
+            assert_eq!(forward_count, backward_count);
+        }
+    }
+
+    #[test]
+    fn basic_board() {
+        let word = b"XMAS";
+        for i in 1..100 {
+            println!("i={i}");
+            let data = word.repeat(i);
+            let board = Board { data, width: word.len(), height: i };
+            println!("{board}");
+            let count = count_word(&board, b"XMAS", true);
+            assert_eq!(count, (i + 2 * i.saturating_sub(3)) as u32);
+        }
+    }
+
+    #[test]
+    fn double_board() {
+        let word = b"XMASAMX";
+        for i in 1..100 {
+            println!("i={i}");
+            let data = word.repeat(i);
+            let board = Board { data, width: word.len(), height: i };
+            println!("{board}");
+            let count = count_word(&board, b"XMAS", true);
+            assert_eq!(count, 2 * (i + 2 * i.saturating_sub(3)) as u32);
+        }
+    }
+
+    #[test]
+    fn double_board2() {
+        let word = b"XMASSAMX";
+        for i in 1..100 {
+            println!("i={i}");
+            let data = word.repeat(i);
+            let board = Board { data, width: word.len(), height: i };
+            println!("{board}");
+            let count = count_word(&board, b"XMAS", true);
+            assert_eq!(count, 2 * (i + 2 * i.saturating_sub(3)) as u32);
+        }
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let e = read_data("XMAS\nSAM\nXMASX\n").unwrap_err();
+        assert_eq!((2, 4), e.location().map(|l| (l.line, l.column)).unwrap());
+        let e = read_data("XMAS\nXMAS\nXMASX\n").unwrap_err();
+        assert_eq!((3, 5), e.location().map(|l| (l.line, l.column)).unwrap());
+        assert!(read_data("").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::{cmp::Ordering, collections::{HashMap, HashSet}};
+use aoc_common::{Answer, Error, Result, Solver};
+use winnow::{ascii::digit1, combinator::{repeat, separated, separated_pair, terminated}, prelude::*, token::literal};
+
+type Rules = HashMap<u32, HashSet<u32>>;
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = (Rules, Vec<Vec<u32>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        sum_valid_midpoints(&input.0, &input.1).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        sum_invalid_midpoints(&input.0, &input.1).into()
+    }
+}
+
+fn sum_valid_midpoints(rules: &Rules, data: &[Vec<u32>]) -> u32 {
+    data.iter()
+        .inspect(|pages| {
+            if pages.len().is_multiple_of(2) {
+                panic!("Unexpected even length page data: {pages:?}");
+            }
+        })
+        .filter(|pages| validate_pages(rules, pages))
+        .map(|pages| midpoint(pages))
+        .sum()
+}
+
+fn validate_pages(rules: &Rules, pages: &[u32]) -> bool {
+    let mut seen: HashSet<u32> = HashSet::new();
+    for page in pages {
+        if let Some(banned_pages) = rules.get(page) {
+            for banned in banned_pages {
+                if seen.contains(banned) {
+                    return false;
+                }
+            }
+        }
+        seen.insert(*page);
+    }
+    true
+}
+
+fn midpoint(pages: &[u32]) -> u32 {
+    let mid = pages.len() / 2;
+    pages[mid]
+}
+
+fn sum_invalid_midpoints(rules: &Rules, data: &[Vec<u32>]) -> u32 {
+    data.iter()
+        .inspect(|pages| {
+            if pages.len().is_multiple_of(2) {
+                panic!("Unexpected even length page data: {pages:?}");
+            }
+        })
+        .filter(|pages| !validate_pages(rules, pages))
+        .map(|pages| fix_order(rules, pages))
+        .map(|pages| midpoint(&pages))
+        .sum()
+}
+
+fn fix_order(rules: &Rules, data: &[u32]) -> Vec<u32> {
+    let mut data = data.to_vec();
+    // Assumes that the rules constitute a partial order
+    data.sort_by(|l, r| {
+        if rules.get(l).is_some_and(|set| set.contains(r)) { return Ordering::Less }
+        if rules.get(r).is_some_and(|set| set.contains(l)) { return Ordering::Greater }
+        Ordering::Equal
+    });
+    data
+}
+
+fn read_input(input: &str) -> Result<(Rules, Vec<Vec<u32>>)> {
+    let (rules, data) = full_input.parse(input).map_err(|e| {
+        Error::new("Expected page ordering rules like `47|53`, a blank line, then page lists like `75,47,61`").at_offset(input, e.offset())
+    })?;
+    // Every list needs a middle page.
+    let lines = input.lines().skip_while(|line| !line.is_empty()).skip(1);
+    if let Some((pages, line)) = data.iter().zip(lines).find(|(pages, _)| pages.len().is_multiple_of(2)) {
+        return Err(Error::new(format!("Expected an odd number of pages, found {}", pages.len())).at_slice(input, line));
+    }
+    Ok((rules, data))
+}
+
+fn full_input(input: &mut &str) -> PResult<(Rules, Vec<Vec<u32>>)> {
+    let rules: Vec<(u32, u32)> = repeat(1.., terminated(rule, literal('\n'))).parse_next(input)?;
+    let rules = rules.into_iter().fold(HashMap::new(), |mut acc: HashMap<u32, HashSet<u32>>, (k, v)| {
+        acc.entry(k).or_default().insert(v);
+        acc
+    });
+    literal('\n').parse_next(input)?;
+    let data: Vec<Vec<u32>> = repeat(1.., terminated(pages, literal('\n'))).parse_next(input)?;
+    Ok((rules, data))
+}
+
+fn rule(input: &mut &str) -> PResult<(u32, u32)> {
+    separated_pair(parse_u32, literal('|'), parse_u32)
+        .parse_next(input)
+}
+
+fn pages(input: &mut &str) -> PResult<Vec<u32>> {
+    separated(1.., parse_u32, literal(','))
+        .parse_next(input)
+}
+
+fn parse_u32(input: &mut &str) -> PResult<u32> {
+    digit1.try_map(|x: &str| x.parse()).parse_next(input)
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::RandomState;
+
+use aoc_common::{Answer, Error, Result, Solver};
+use grid::{Cardinality, Direction, Grid, GridRead, GridWrite, Journaled, Markers, Outcome, ParseError, Path, PathStyle, Position, Simulator, Step};
+
+#[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Copy, Clone)]
+enum Square {
+    Empty,
+    Obstacle
+}
+
+/// A map on which the guard eventually leaves, which `read_data` checks.
+#[derive(Debug,  Clone)]
+pub struct Board {
+    grid: Grid<Square>,
+    guard_init: Step,
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Square::Empty => write!(f, "."),
+            Square::Obstacle => write!(f, "#"),
+        }
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let pos = Position::new(x,y);
+                if pos == self.guard_init.pos {
+                    write!(f, "{}", self.guard_init.dir.arrow())?;
+                } else {
+                    write!(f, "{}", self.grid[&pos])?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The map with the guard's patrol route drawn over it.
+pub fn render_patrol(board: &Board) -> String {
+    patrol(&board.grid, &board.guard_init).render(&board.grid, PathStyle::Arrows)
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = Board;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_walk_board(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_potential_loops(input).into()
+    }
+}
+
+fn count_walk_board(board: &Board) -> u32 {
+    let path = patrol(&board.grid, &board.guard_init);
+    let set: HashSet<&Position, RandomState> = HashSet::from_iter(path.positions());
+    set.len() as u32
+}
+
+fn count_potential_loops(board: &Board) -> u32 {
+    let path = patrol(&board.grid, &board.guard_init);
+    let mut lab = Journaled::new(board.grid.clone());
+    // Positions the guard has already passed through. An obstacle there would have changed the route before reaching the current step.
+    let mut passed: HashSet<&Position> = HashSet::new();
+    let mut count: u32 = 0;
+
+    for window in path.steps().windows(2) {
+        // Each step considers the guard's next position as the location of a potential obstacle.
+        let (guard, obstacle_pos) = (&window[0], &window[1].pos);
+        passed.insert(&guard.pos);
+        if *obstacle_pos != board.guard_init.pos && !passed.contains(obstacle_pos) {
+            debug_assert!(lab[obstacle_pos] == Square::Empty);
+            let checkpoint = lab.checkpoint();
+            lab.set(obstacle_pos, Square::Obstacle);
+            if is_loop(&lab, guard) { count += 1; }
+            lab.rollback(checkpoint);
+        }
+    }
+    count
+}
+
+/// The guard's next state: one square forward, or a turn to the right in place if an obstacle is ahead. Returns `None` once the guard leaves the map.
+fn step<G: GridRead<Cell = Square>>(grid: &G, guard: &Step) -> Option<Step> {
+    let next_pos = (&guard.pos + &guard.dir.to_offset())?;
+    match grid.get(&next_pos)? {
+        Square::Empty => Some(Step { pos: next_pos, dir: guard.dir }),
+        Square::Obstacle => Some(Step { pos: guard.pos.clone(), dir: guard.dir.turn_right() }),
+    }
+}
+
+/// Every state of the guard from `start` until it leaves the map. Must only be called where the guard does leave, such as from a [`Board`]'s start.
+fn patrol(grid: &Grid<Square>, start: &Step) -> Path {
+    std::iter::successors(Some(start.clone()), |guard| step(grid, guard)).collect()
+}
+
+/// Whether the guard walks in a loop forever from `start`, rather than leaving the map.
+fn is_loop<G: GridRead<Cell = Square>>(grid: &G, start: &Step) -> bool {
+    matches!(Simulator::new(start.clone(), |guard| step(grid, guard)).brent(), Outcome::Cycle { .. })
+}
+
+fn read_data(input: &str) -> Result<Board> {
+    // Could accept the guard facing other directions here, but it doesn't seem necessary
+    let marked = Markers::new()
+        .marker("^", Square::Empty, Cardinality::ExactlyOne)
+        .parse(input, |c| match c {
+            '.' => Some(Square::Empty),
+            '#' => Some(Square::Obstacle),
+            _ => None,
+        })
+        .map_err(|e| match e {
+            ParseError::UnexpectedChar { pos, c } => {
+                Error::new(format!("Unexpected map character {c:?}, expected '.', '#' or '^'")).at_slice(input, cell(input, pos.x(), pos.y()))
+            },
+            ParseError::RaggedRow { row, expected, found } => {
+                Error::new(format!("Expected {expected} squares on every line, found {found}")).at_slice(input, cell(input, expected, row))
+            },
+            ParseError::MarkerCount { found: 0, .. } => Error::new("No guard '^' found in the map"),
+            ParseError::MarkerCount { .. } => {
+                let (second, _) = input.match_indices('^').nth(1).expect("more than one guard");
+                Error::new("Found a second guard '^', expected only one").at_offset(input, second)
+            },
+        })?;
+    let pos = marked.first('^').expect("exactly one guard").clone();
+    let guard_init = Step { pos, dir: Direction::Up };
+    if is_loop(&marked.grid, &guard_init) {
+        let guard = input.find('^').expect("exactly one guard");
+        return Err(Error::new("The guard walks in a loop and never leaves the map").at_offset(input, guard));
+    }
+    Ok(Board { grid: marked.grid, guard_init })
+}
+
+/// The text of character `x` of line `y` onwards, or the end of the line if it is shorter.
+fn cell(input: &str, x: usize, y: usize) -> &str {
+    let line = input.lines().nth(y).expect("grid rows come from input lines");
+    &line[line.char_indices().nth(x).map_or(line.len(), |(i, _)| i)..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_leaves_immediately() {
+        let board = read_data(".^.\n...\n").unwrap();
+        assert_eq!(1, count_walk_board(&board));
+        assert_eq!(0, count_potential_loops(&board));
+    }
+
+    #[test]
+    fn guard_already_loops() {
+        let e = read_data(".#..\n...#\n#^..\n..#.\n").unwrap_err();
+        assert_eq!((3, 2), e.location().map(|l| (l.line, l.column)).unwrap());
+    }
+}
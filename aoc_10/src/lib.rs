@@ -0,0 +1,108 @@
+use std::{collections::VecDeque, fmt::Display};
+use aoc_common::{Answer, Error, Result, Solver};
+use bit_set::BitSet;
+use grid::{Grid, GridBuilder, Offset, ParseError, Position};
+
+type Token = u8;
+
+pub struct Board {
+    grid: Grid<Token>,
+}
+
+impl Board {
+    fn find_digit(&self, digit: Token) -> Vec<Position> {
+        let mut r = Vec::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let pos = Position::new(x,y);
+                if self.grid[&pos] == digit {
+                    r.push(pos);
+                }
+            }
+        }
+        r
+    }
+
+    fn count_trailheads_scores(&self) -> usize {
+        // Note: this algorithm proceeds backwards from the summits
+        let directions = [Offset::new(0,1), Offset::new(0,-1), Offset::new(1,0), Offset::new(-1,0)];
+        let summits = self.find_digit(9);
+        let mut visited: Grid<BitSet> = self.grid.map(|_| BitSet::with_capacity(summits.len()));
+
+        let mut queue: VecDeque<(usize, Position)> = VecDeque::from_iter(summits.iter().enumerate().map(|(i, pos)| (i, pos.clone())));
+        while let Some((i, pos)) = queue.pop_front() {
+            if visited[&pos].insert(i) {
+                let elevation = self.grid[&pos];
+                for diff in directions.iter() {
+                    if let Some(next_pos) = &pos + diff {
+                        if self.grid.in_bounds(&next_pos) && self.grid[&next_pos] + 1 == elevation {
+                            queue.push_back((i, next_pos));
+                        }
+                    }
+                }
+            }
+        }
+
+        let trailheads = self.find_digit(0);
+        trailheads.iter().map(|pos| visited[pos].len()).sum()
+    }
+
+    fn count_trailheads_ratings(&self) -> usize {
+        self.find_digit(0).iter().map(|p| self.count_trailhead_rating(p)).sum()
+    }
+
+    fn count_trailhead_rating(&self, pos: &Position) -> usize {
+        // Every trail climbs exactly one step at a time, so all trails from a trailhead to a given summit have the same length.
+        // That makes every distinct trail a shortest path to its summit.
+        let paths = self.grid.shortest_paths([pos.clone()], |from, to| *to == from + 1);
+        let count: u128 = paths.reached()
+            .filter(|pos| self.grid[pos] == 9)
+            .map(|summit| paths.count_paths(summit).expect("path count fits in u128"))
+            .sum();
+        count as usize
+    }
+
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Board;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.count_trailheads_scores().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.count_trailheads_ratings().into()
+    }
+}
+
+fn read_data(input: &str) -> Result<Board> {
+    let mut builder: GridBuilder<Token> = GridBuilder::new();
+    for line in input.lines() {
+        let row = line.char_indices().map(|(i, c)| {
+            c.to_digit(10).map(|d| d as Token).ok_or_else(|| Error::new(format!("Expected an elevation digit, found {c:?}")).at_slice(input, &line[i..]))
+        }).collect::<Result<Vec<Token>>>()?;
+        builder.push_row(row).map_err(|e| match e {
+            ParseError::RaggedRow { expected, found, .. } => {
+                Error::new(format!("Expected {expected} digits on every line, found {found}")).at_slice(input, &line[expected.min(line.len())..])
+            },
+            e => Error::new(e.to_string()).at_slice(input, line),
+        })?;
+    }
+    if builder.height() == 0 {
+        return Err(Error::new("Expected non-empty board"));
+    }
+    Ok(Board{ grid: builder.build() })
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use aoc_common::{Answer, Error, Result, Solver};
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut data = input.clone();
+        blink_n(&mut data, 25);
+        data.len().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_blink_all_n(input, 75).into()
+    }
+}
+
+fn blink_n(data: &mut Vec<u64>, count: u32) {
+    for _ in 0..count {
+        blink(data);
+        // println!("{data:?}");
+    }
+}
+
+fn blink(data: &mut Vec<u64>) {
+    let mut i = 0;
+    while i < data.len() {
+        match data[i] {
+            0 => data[i] = 1,
+            x if digits(x).is_multiple_of(2) => {
+                let (j, k) = split_stone(x);
+                data.insert(i, j);
+                data[i+1] = k;
+                i += 1;
+            },
+            x => data[i] = x * 2024,
+        }
+        i += 1;
+    }
+}
+
+fn count_blink_all_n(data: &[u64], count: u32) -> u64 {
+    let mut memo: HashMap<(u64, u32), u64> = HashMap::new();
+    let mut sum: u64 = 0;
+    for stone in data {
+        sum += count_blink_n(*stone, count, &mut memo);
+    }
+    sum
+}
+
+fn count_blink_n(stone: u64, count: u32, memo: &mut HashMap<(u64, u32), u64>) -> u64 {
+    if count == 0 {
+        return 1;
+    }
+    if let Some(r) = memo.get(&(stone, count)) {
+        return *r;
+    }
+    let r = match stone {
+        0 => {
+            count_blink_n(1, count - 1, memo)
+        },
+        x if digits(x).is_multiple_of(2) => {
+            let (j, k) = split_stone(x);
+            count_blink_n(j, count - 1, memo) + count_blink_n(k, count - 1, memo)
+        },
+        x => {
+            count_blink_n(x * 2024, count - 1, memo)
+        },
+    };
+    memo.insert((stone, count), r);
+    r
+}
+
+fn split_stone(x: u64) -> (u64, u64) {
+    let divisor = 10u64.pow(digits(x) / 2);
+    (x / divisor, x % divisor)
+}
+
+fn digits(x: u64) -> u32 {
+    (x as f64).log10().trunc() as u32 + 1
+}
+
+fn read_data(input: &str) -> Result<Vec<u64>> {
+    input.split_ascii_whitespace().map(|s| {
+        s.parse::<u64>().map_err(|e| Error::new(format!("Invalid stone {s:?}: {e}")).at_slice(input, s))
+    }).collect::<Result<Vec<u64>>>()
+}
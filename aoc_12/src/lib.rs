@@ -0,0 +1,192 @@
+use std::{collections::{HashMap, HashSet}, fmt::Display};
+
+use aoc_common::{Answer, Error, Result, Solver};
+use enumset::{EnumSet, EnumSetType};
+use grid::{Grid, GridBuilder, Offset, ParseError, Position};
+
+type Token = u8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    grid: Grid<Token>
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid.map(|b| *b as char))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Region {
+    members: HashSet<Position>,
+}
+
+impl Region {
+    fn new() -> Self {
+        Region { members: HashSet::new() }
+    }
+
+    fn perimeter(&self) -> usize {
+        let mut perimeter = 0;
+        for pos in self.members.iter() {
+            let adjacencies = adjacent(pos).iter()
+                .filter(|adj| self.members.contains(adj))
+                .count();
+            perimeter += 4 - adjacencies;
+        }
+        perimeter
+    }
+
+    fn count_sides(&self) -> usize {
+        let adjacencies: HashMap<&Position, EnumSet<Direction>> = self.members.iter()
+            .map(|pos| {
+                let mut set: EnumSet<Direction> = EnumSet::new();
+                for dir in Direction::ALL {
+                    if let Some(adj) = pos + &dir.to_offset() {
+                        if self.members.contains(&adj) {
+                            set.insert(dir);
+                        }
+                    }
+                }
+                (pos, set)
+            })
+            .collect();
+
+        // A corner is formed when an adjacent tile in a perpendicular direction lacks the same edge as this tile (including tiles outside this region).
+        // An edge is a direction pointing outside this region (including the edge of the map). This is the complement of the adjacency set.
+        // If multiple such tiles exist, then multiple corners are formed.
+        // The number of corners is equal to the number of sides.
+        //
+        // This algorithm double-counts corners because each corner corresponds to two edges: either two perpendicular edges of the same tile (for outside corners) or
+        // perpendicular edges between diagonal tiles (for inside corners).
+        // To correct for this, we divide by two at the end.
+        let mut corners: usize = 0;
+        for (pos, adj) in adjacencies.iter() {
+            let non_adj = adj.complement();
+            for edge in non_adj.iter() {
+                for dir in edge.perpendicular() {
+                    if let Some(adj_pos) = *pos + &dir.to_offset() {
+                        if let Some(other_adj) = adjacencies.get(&adj_pos) {
+                            if other_adj.complement().contains(edge) {
+                                continue;
+                            }
+                        }
+                    }
+                    corners += 1;
+                }
+            }
+        }
+        corners / 2
+    }
+
+    fn area(&self) -> usize {
+        self.members.len()
+    }
+
+    fn price_by_perimeter(&self) -> usize {
+        self.perimeter() * self.area()
+    }
+
+    fn price_by_sides(&self) -> usize {
+        // self.perimeter() * self.area()
+        self.count_sides() * self.area()
+    }
+}
+
+#[derive(Debug, EnumSetType)]
+enum Direction {
+    Up, Down, Left, Right
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [Direction::Up, Direction:: Down, Direction::Left, Direction::Right];
+
+    fn to_offset(self) -> Offset {
+        match self {
+            Direction::Up => Offset::new(0, -1),
+            Direction::Down => Offset::new(0, 1),
+            Direction::Left => Offset::new(-1, 0),
+            Direction::Right => Offset::new(1, 0),
+        }
+    }
+
+    fn perpendicular(&self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Board;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        find_regions(input).iter().map(Region::price_by_perimeter).sum::<usize>().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        find_regions(input).iter().map(Region::price_by_sides).sum::<usize>().into()
+    }
+}
+
+fn find_regions(board: &Board) -> Vec<Region> {
+    let mut r = Vec::new();
+    let mut visited = board.grid.map(|_| false);
+    for y in 0..board.grid.height() {
+        for x in 0..board.grid.width() {
+            let pos = Position::new(x,y);
+            if !visited[&pos] {
+                let mut region = Region::new();
+                fill_region(board, &mut region, &mut visited, &pos);
+                r.push(region);
+            }
+        }
+    }
+    r
+}
+
+fn fill_region(board: &Board, region: &mut Region, visited: &mut Grid<bool>, pos: &Position) {
+    if visited[pos] {
+        return;
+    }
+    region.members.insert(pos.clone());
+    visited[pos] = true;
+    for next_pos in adjacent(pos) {
+        if board.grid.get(&next_pos) == Some(&board.grid[pos]) {
+            fill_region(board, region, visited, &next_pos);
+        }
+    }
+}
+
+fn adjacent(pos: &Position) -> Vec<Position> {
+    Direction::ALL.iter()
+        .filter_map(|d| pos + &d.to_offset())
+        .collect()
+}
+
+fn read_data(input: &str) -> Result<Board> {
+    let mut builder: GridBuilder<Token> = GridBuilder::new();
+    for line in input.lines() {
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(Error::new(format!("Unexpected non-ASCII plant {c:?}")).at_slice(input, &line[i..]));
+        }
+        builder.push_row(line.bytes()).map_err(|e| match e {
+            ParseError::RaggedRow { expected, found, .. } => {
+                Error::new(format!("Expected {expected} plants on every line, found {found}")).at_slice(input, &line[expected.min(line.len())..])
+            },
+            e => Error::new(e.to_string()).at_slice(input, line),
+        })?;
+    }
+    if builder.height() == 0 {
+        return Err(Error::new("Expected non-empty board"));
+    }
+    Ok(Board{ grid: builder.build() })
+}
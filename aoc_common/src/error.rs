@@ -0,0 +1,124 @@
+use std::{fmt::{Debug, Display}, io, path::{Path, PathBuf}};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Where in the input an error was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Counting from 1.
+    pub line: usize,
+    /// Counting characters from 1.
+    pub column: usize,
+    /// The full text of the line.
+    pub snippet: String,
+}
+
+/// An error from reading a puzzle input, which can point at the offending spot in the input and name the file it came from.
+///
+/// `Debug` prints the same diagnostic as `Display`, so that a `main` returning this error prints something readable.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+    location: Option<Location>,
+    file: Option<PathBuf>,
+}
+
+impl Error {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Error { message: message.into(), location: None, file: None }
+    }
+
+    /// Points the error at byte `offset` of `input`, which is clamped to the end of the input.
+    pub fn at_offset(mut self, input: &str, offset: usize) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        self.location = Some(Location {
+            line: input[..start].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            snippet: input[start..end].trim_end_matches('\r').into(),
+        });
+        self
+    }
+
+    /// Points the error at the start of `part`, which must be a slice of `input`, such as a line or a token split from it.
+    pub fn at_slice(self, input: &str, part: &str) -> Self {
+        let offset = (part.as_ptr() as usize).checked_sub(input.as_ptr() as usize).filter(|offset| *offset <= input.len());
+        let offset = offset.expect("part must be a slice of input");
+        self.at_offset(input, offset)
+    }
+
+    pub fn with_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.file = Some(file.as_ref().into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+}
+
+/// Keeps only the message; the location is unknown.
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::new(e.to_string())
+    }
+}
+
+/// Prints `file:line:column: message` followed by the line with a marker under the column, omitting whatever is unknown.
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, "{}:{}:", location.line, location.column)?;
+        }
+        if self.file.is_some() || self.location.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(Location { line, column, snippet }) = &self.location {
+            let margin = " ".repeat(line.to_string().len());
+            write!(f, "\n{margin} |\n{line} | {snippet}\n{margin} | {:>column$}", "^")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let input = "abc\nde✓fg\r\nh";
+        let line = input.lines().nth(1).unwrap();
+        let e = Error::new("Bad").at_slice(input, &line[5..]);
+        assert_eq!(Some(&Location { line: 2, column: 4, snippet: "de✓fg".into() }), e.location());
+        assert_eq!("x.txt:2:4: Bad\n  |\n2 | de✓fg\n  |    ^", e.with_file("x.txt").to_string());
+        let end = Error::new("Missing").at_offset(input, 100);
+        assert_eq!(Some(&Location { line: 3, column: 2, snippet: "h".into() }), end.location());
+        assert_eq!(Some(&Location { line: 1, column: 1, snippet: "".into() }), Error::new("Empty").at_offset("", 0).location());
+        assert_eq!("Empty board", Error::new("Empty board").to_string());
+    }
+}
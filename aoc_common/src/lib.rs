@@ -0,0 +1,146 @@
+//! Pieces shared by every day's solution, so that runners, tests and benchmarks can drive any day the same way.
+
+use std::{fmt::Display, time::{Duration, Instant}};
+
+mod error;
+
+pub use error::{Error, Location, Result};
+
+/// A solution to one day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solver {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// The part was never solved, usually because the code was rewritten in place for the other part.
+    Unsolved,
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    Answer::Number(x as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.into())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(x) => write!(f, "{x}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Which parts of a puzzle to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+impl Parts {
+    pub fn includes(self, part: usize) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Part1 => part == 1,
+            Parts::Part2 => part == 2,
+        }
+    }
+}
+
+/// The answers from [`run`] with the time taken by each step.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub parse: Duration,
+    /// The part number, its answer and how long it took, for each part that was run.
+    pub parts: Vec<(usize, Answer, Duration)>,
+}
+
+impl Report {
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|(_, _, elapsed)| *elapsed).sum::<Duration>()
+    }
+}
+
+/// Parses `input` and runs the selected parts on it, timing each step.
+pub fn run<S: Solver>(input: &str, parts: Parts) -> Result<Report> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+    let mut report = Report { parse, parts: Vec::new() };
+    for (part, solve) in [(1, S::part1 as fn(&S::Input) -> Answer), (2, S::part2)] {
+        if parts.includes(part) {
+            let start = Instant::now();
+            let answer = solve(&parsed);
+            report.parts.push((part, answer, start.elapsed()));
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solver for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input.split_whitespace().map(|s| s.parse().map_err(|_| Error::new("Expected an integer").at_slice(input, s))).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn run_parts() {
+        let report = run::<Sum>("1 2 -4", Parts::Both).unwrap();
+        let answers: Vec<(usize, Answer)> = report.parts.into_iter().map(|(part, answer, _)| (part, answer)).collect();
+        assert_eq!(vec![(1, Answer::Number(-1)), (2, Answer::Unsolved)], answers);
+        assert_eq!(1, run::<Sum>("1", Parts::Part2).unwrap().parts.len());
+        assert_eq!(3, run::<Sum>("1 x", Parts::Both).unwrap_err().location().unwrap().column);
+        assert_eq!("unsolved", Answer::Unsolved.to_string());
+        assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+    }
+}
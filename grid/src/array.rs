@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+use core::{fmt::Display, ops::{Index, IndexMut}};
+
+use crate::{Grid, GridRead, GridWrite, Position};
+
+/// A grid whose dimensions are fixed at compile time, stored inline as an array of rows.
+///
+/// Being free of allocations and cheap to hash and compare, it suits small patterns, kernels and puzzle pieces,
+/// and states that are stored in bulk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayGrid<T, const W: usize, const H: usize> {
+    rows: [[T; W]; H],
+}
+
+impl <T, const W: usize, const H: usize> ArrayGrid<T, W, H> {
+    pub const WIDTH: usize = W;
+    pub const HEIGHT: usize = H;
+
+    pub fn new(rows: [[T; W]; H]) -> Self {
+        ArrayGrid { rows }
+    }
+
+    /// Builds the grid by calling `f` on every position, in row-major order.
+    pub fn from_fn<F: FnMut(Position) -> T>(mut f: F) -> Self {
+        ArrayGrid { rows: core::array::from_fn(|y| core::array::from_fn(|x| f(Position(x, y)))) }
+    }
+
+    pub fn width(&self) -> usize {
+        W
+    }
+
+    pub fn height(&self) -> usize {
+        H
+    }
+
+    pub fn in_bounds(&self, pos: &Position) -> bool {
+        pos.0 < W && pos.1 < H
+    }
+
+    pub fn get(&self, pos: &Position) -> Option<&T> {
+        self.rows.get(pos.1)?.get(pos.0)
+    }
+
+    pub fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        self.rows.get_mut(pos.1)?.get_mut(pos.0)
+    }
+
+    pub fn rows(&self) -> &[[T; W]; H] {
+        &self.rows
+    }
+
+    pub fn into_rows(self) -> [[T; W]; H] {
+        self.rows
+    }
+
+    /// The cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.rows.iter().flatten()
+    }
+
+    pub fn map<F, S>(&self, mut f: F) -> ArrayGrid<S, W, H>
+    where
+        F: FnMut(&T) -> S,
+    {
+        ArrayGrid { rows: core::array::from_fn(|y| core::array::from_fn(|x| f(&self.rows[y][x]))) }
+    }
+}
+
+impl <T: Default, const W: usize, const H: usize> Default for ArrayGrid<T, W, H> {
+    fn default() -> Self {
+        ArrayGrid::from_fn(|_| T::default())
+    }
+}
+
+impl <T, const W: usize, const H: usize> GridRead for ArrayGrid<T, W, H> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        W
+    }
+
+    fn height(&self) -> usize {
+        H
+    }
+
+    fn get(&self, pos: &Position) -> Option<&T> {
+        ArrayGrid::get(self, pos)
+    }
+}
+
+impl <T, const W: usize, const H: usize> GridWrite for ArrayGrid<T, W, H> {
+    fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        ArrayGrid::get_mut(self, pos)
+    }
+}
+
+impl <T, const W: usize, const H: usize> Index<&Position> for ArrayGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, pos: &Position) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+    }
+}
+
+impl <T, const W: usize, const H: usize> IndexMut<&Position> for ArrayGrid<T, W, H> {
+    fn index_mut(&mut self, pos: &Position) -> &mut Self::Output {
+        self.get_mut(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+    }
+}
+
+impl <T: Display, const W: usize, const H: usize> Display for ArrayGrid<T, W, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (y, row) in self.rows.iter().enumerate() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            if y < H - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl <T, const W: usize, const H: usize> From<ArrayGrid<T, W, H>> for Grid<T> {
+    fn from(grid: ArrayGrid<T, W, H>) -> Self {
+        let data: Vec<T> = grid.rows.into_iter().flatten().collect();
+        Grid::new(data, W, H)
+    }
+}
+
+/// Fails if the dimensions differ, handing back the original grid.
+impl <T, const W: usize, const H: usize> TryFrom<Grid<T>> for ArrayGrid<T, W, H> {
+    type Error = Grid<T>;
+
+    fn try_from(grid: Grid<T>) -> Result<Self, Self::Error> {
+        if grid.width != W || grid.height != H {
+            return Err(grid);
+        }
+        let mut cells = grid.data.into_iter();
+        Ok(ArrayGrid::from_fn(|_| cells.next().expect("grid has W * H cells")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_round_trip(cells: [[u8; 4]; 3]) {
+            let array = ArrayGrid::new(cells);
+            let grid = Grid::from(array);
+            assert!(grid.indexed_iter().all(|(pos, cell)| array[&pos] == *cell));
+            assert_eq!(Ok(array), ArrayGrid::try_from(grid));
+        }
+    }
+
+    #[test]
+    fn pattern() {
+        let kernel = ArrayGrid::new([[b'M', b'.', b'S'], [b'.', b'A', b'.'], [b'M', b'.', b'S']]);
+        let grid: Grid<u8> = ["XMASM", "MMAS.", ".XAM.", "MMXSS"].iter().map(|row| row.bytes()).collect();
+        let matches: Vec<Position> = (0..=grid.height() - 3)
+            .flat_map(|y| (0..=grid.width() - 3).map(move |x| Position::new(x,y)))
+            .filter(|corner| kernel.indexed_iter().all(|(pos, k)| *k == b'.' || grid[&Position::new(corner.x() + pos.x(), corner.y() + pos.y())] == *k))
+            .collect();
+        assert_eq!(vec![Position::new(1,1)], matches);
+
+        let mut small: ArrayGrid<u8, 2, 2> = ArrayGrid::default();
+        small.set(&Position::new(1,0), 7);
+        assert_eq!(None, small.set(&Position::new(2,0), 7));
+        assert_eq!("07\n00", small.to_string());
+        assert!(ArrayGrid::<u8, 3, 2>::try_from(Grid::from(small)).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+use crate::{Grid, ParseError};
+
+/// Builds a [`Grid`] one row at a time, checking that every row has the same width as the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridBuilder<T> {
+    data: Vec<T>,
+    width: Option<usize>,
+    height: usize,
+}
+
+impl <T> Default for GridBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> GridBuilder<T> {
+    pub fn new() -> Self {
+        GridBuilder { data: Vec::new(), width: None, height: 0 }
+    }
+
+    /// Appends a row. If its width differs from the earlier rows, the row is discarded and an error is returned.
+    pub fn push_row<I: IntoIterator<Item = T>>(&mut self, row: I) -> Result<(), ParseError> {
+        let start = self.data.len();
+        self.data.extend(row);
+        let found = self.data.len() - start;
+        match self.width {
+            Some(expected) if expected != found => {
+                self.data.truncate(start);
+                return Err(ParseError::RaggedRow { row: self.height, expected, found });
+            },
+            _ => self.width = Some(found),
+        }
+        self.height += 1;
+        Ok(())
+    }
+
+    /// The width of the rows pushed so far, or `None` if there are none yet.
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Finishes the grid. A builder with no rows produces an empty 0x0 grid.
+    pub fn build(self) -> Grid<T> {
+        Grid::new(self.data, self.width.unwrap_or(0), self.height)
+    }
+}
+
+impl <T> Grid<T> {
+    /// Collects rows into a grid, failing on the first row whose width differs from the first row.
+    pub fn try_from_rows<I, R>(rows: I) -> Result<Grid<T>, ParseError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut builder = GridBuilder::new();
+        for row in rows {
+            builder.push_row(row)?;
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Collects rows into a grid. Panics if the rows are not all the same width; use [`Grid::try_from_rows`] to handle that case.
+impl <T, R: IntoIterator<Item = T>> FromIterator<R> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(rows: I) -> Self {
+        Grid::try_from_rows(rows).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// Appends rows to the bottom of the grid. An empty grid takes the width of the first row.
+/// Panics if a row's width differs from the grid's.
+impl <T, R: IntoIterator<Item = T>> Extend<R> for Grid<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, rows: I) {
+        let mut builder = GridBuilder {
+            data: core::mem::take(&mut self.data),
+            width: if self.height == 0 { None } else { Some(self.width) },
+            height: self.height,
+        };
+        let result = rows.into_iter().try_for_each(|row| builder.push_row(row));
+        *self = builder.build();
+        if let Err(e) = result {
+            panic!("{e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let mut builder = GridBuilder::new();
+        builder.push_row(b"abc".iter().copied()).unwrap();
+        builder.push_row(b"def".iter().copied()).unwrap();
+        assert_eq!(Err(ParseError::RaggedRow { row: 2, expected: 3, found: 2 }), builder.push_row(b"gh".iter().copied()));
+        assert_eq!(Some(3), builder.width());
+        assert_eq!(Grid::new(b"abcdef".to_vec(), 3, 2), builder.build());
+    }
+
+    #[test]
+    fn collect_rows() {
+        let grid: Grid<char> = "ab\ncd\nef".lines().map(|line| line.chars()).collect();
+        assert_eq!(Grid::new(vec!['a', 'b', 'c', 'd', 'e', 'f'], 2, 3), grid);
+        assert!(Grid::try_from_rows(["ab", "c"].map(|line| line.chars())).is_err());
+        let empty = Grid::<u8>::try_from_rows(Vec::<Vec<u8>>::new()).unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+    }
+
+    #[test]
+    fn extend() {
+        let mut grid: Grid<u8> = Grid::new(Vec::new(), 0, 0);
+        grid.extend([vec![1, 2]]);
+        grid.extend([vec![3, 4], vec![5, 6]]);
+        assert_eq!(Grid::new(vec![1, 2, 3, 4, 5, 6], 2, 3), grid);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_ragged() {
+        let mut grid = Grid::new(vec![1, 2], 2, 1);
+        grid.extend([vec![3]]);
+    }
+}
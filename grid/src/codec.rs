@@ -0,0 +1,341 @@
+//! Compact file formats for saving and reloading grids.
+//!
+//! Text grids (`Grid<u8>`) can be written as run-length encoded text: a `<width>x<height>` header line followed by one line per row,
+//! where each run of identical cells is written as `<count>*<cell>`. For example, `..##.` becomes `2*.2*#1*.`.
+//!
+//! Any grid whose cells implement [`CellCodec`] can be written in a binary format: the magic bytes `GRID`, a version byte,
+//! the width and height as little-endian `u64`s, then runs of identical cells as a LEB128 count followed by the encoded cell.
+
+use std::{io::{self, BufRead, Read, Write}, mem::size_of};
+
+use crate::Grid;
+
+const MAGIC: &[u8; 4] = b"GRID";
+const VERSION: u8 = 1;
+
+/// Conversion of a single cell to and from bytes, for use by [`Grid::write_binary`] and [`Grid::read_binary`].
+pub trait CellCodec: Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the front of `input`, advancing past the bytes consumed. Returns `None` for truncated or invalid data.
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_cell_codec_int {
+    ($($t:ty),*) => {
+        $(
+            impl CellCodec for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    let (bytes, rest) = input.split_first_chunk()?;
+                    *input = rest;
+                    Some(<$t>::from_le_bytes(*bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_cell_codec_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Stored as a `u64` so that files are portable between platforms.
+impl CellCodec for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out)
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        u64::decode(input)?.try_into().ok()
+    }
+}
+
+/// Stored as an `i64` so that files are portable between platforms.
+impl CellCodec for isize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as i64).encode(out)
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        i64::decode(input)?.try_into().ok()
+    }
+}
+
+impl CellCodec for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8)
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl CellCodec for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u32).encode(out)
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        char::from_u32(u32::decode(input)?)
+    }
+}
+
+impl <T: CellCodec> CellCodec for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            None => false.encode(out),
+            Some(x) => {
+                true.encode(out);
+                x.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        if bool::decode(input)? { Some(Some(T::decode(input)?)) } else { Some(None) }
+    }
+}
+
+impl <A: CellCodec, B: CellCodec> CellCodec for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        Some((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint(mut x: u64, out: &mut Vec<u8>) {
+    while x >= 0x80 {
+        out.push((x as u8) | 0x80);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut r: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        r |= ((byte & 0x7f) as u64).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(r);
+        }
+    }
+    None
+}
+
+/// Splits the cells into maximal runs of equal values, yielding each run's first cell and length.
+fn runs<T: PartialEq>(cells: &[T]) -> impl Iterator<Item = (&T, usize)> {
+    let mut rest = cells;
+    std::iter::from_fn(move || {
+        let first = rest.first()?;
+        let len = rest.iter().take_while(|x| *x == first).count();
+        rest = &rest[len..];
+        Some((first, len))
+    })
+}
+
+/// Computes `width * height`, rejecting dimensions whose cells could never fit in memory.
+fn checked_area<T>(width: u64, height: u64) -> io::Result<usize> {
+    width.checked_mul(height)
+        .and_then(|area| usize::try_from(area).ok())
+        .filter(|area| area.checked_mul(size_of::<T>()).is_some_and(|bytes| bytes <= isize::MAX as usize))
+        .ok_or_else(|| invalid_data("Grid dimensions are too large"))
+}
+
+/// Appends a run of `len` copies of `cell`. The cells come from the input rather than its header, so the data only grows as
+/// far as the input actually reaches, and a run too long to allocate is an error rather than an abort.
+fn push_run<T: Clone>(data: &mut Vec<T>, len: usize, cell: T) -> io::Result<()> {
+    data.try_reserve(len).map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "Grid is too large to allocate"))?;
+    data.resize(data.len() + len, cell);
+    Ok(())
+}
+
+impl <T: CellCodec + PartialEq> Grid<T> {
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        (self.width as u64).encode(&mut out);
+        (self.height as u64).encode(&mut out);
+        for (cell, len) in runs(&self.data) {
+            write_varint(len as u64, &mut out);
+            cell.encode(&mut out);
+        }
+        writer.write_all(&out)
+    }
+}
+
+impl <T: CellCodec + Clone> Grid<T> {
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Grid<T>> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        let mut input = &buf[..];
+        if input.split_off(..MAGIC.len()) != Some(&MAGIC[..]) {
+            return Err(invalid_data("Missing grid header"));
+        }
+        match u8::decode(&mut input) {
+            Some(VERSION) => (),
+            Some(v) => return Err(invalid_data(&format!("Unsupported grid format version {v}"))),
+            None => return Err(invalid_data("Truncated grid header")),
+        }
+        let (width, height) = u64::decode(&mut input).zip(u64::decode(&mut input)).ok_or_else(|| invalid_data("Truncated grid header"))?;
+        let area = checked_area::<T>(width, height)?;
+
+        let mut data = Vec::new();
+        while !input.is_empty() {
+            let len = read_varint(&mut input).ok_or_else(|| invalid_data("Invalid run length"))?;
+            let cell = T::decode(&mut input).ok_or_else(|| invalid_data("Invalid cell value"))?;
+            if len > (area - data.len()) as u64 {
+                return Err(invalid_data("Cell data exceeds grid dimensions"));
+            }
+            push_run(&mut data, len as usize, cell)?;
+        }
+        if data.len() != area {
+            return Err(invalid_data("Cell data does not fill grid dimensions"));
+        }
+        Ok(Grid { data, width: width as usize, height: height as usize })
+    }
+}
+
+impl Grid<u8> {
+    /// Fails without writing anything if any cell is a newline, which the text format cannot represent.
+    pub fn write_rle<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        if self.data.contains(&b'\n') {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Run-length encoded grids cannot contain newlines"));
+        }
+        writeln!(writer, "{}x{}", self.width, self.height)?;
+        for y in 0..self.height {
+            for (cell, len) in runs(&self.data[y * self.width..(y + 1) * self.width]) {
+                write!(writer, "{len}*")?;
+                writer.write_all(&[*cell])?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_rle<R: BufRead>(reader: R) -> io::Result<Grid<u8>> {
+        let mut lines = reader.split(b'\n');
+        let header = lines.next().ok_or_else(|| invalid_data("Missing grid header"))??;
+        let (width, height) = std::str::from_utf8(&header).ok()
+            .and_then(|header| header.split_once('x'))
+            .and_then(|(w, h)| Some((w.parse::<u64>().ok()?, h.parse::<u64>().ok()?)))
+            .ok_or_else(|| invalid_data("Invalid grid header"))?;
+        checked_area::<u8>(width, height)?;
+
+        let mut data = Vec::new();
+        for _ in 0..height {
+            let line = lines.next().ok_or_else(|| invalid_data("Missing grid row"))??;
+            let start = data.len();
+            let mut input = &line[..];
+            while !input.is_empty() {
+                let digits = input.iter().take_while(|b| b.is_ascii_digit()).count();
+                let len: usize = std::str::from_utf8(&input[..digits]).unwrap().parse().map_err(|_| invalid_data("Invalid run length"))?;
+                match input[digits..] {
+                    [b'*', cell, ..] if len <= width as usize - (data.len() - start) => push_run(&mut data, len, cell)?,
+                    [b'*', _, ..] => return Err(invalid_data("Row is longer than grid width")),
+                    _ => return Err(invalid_data("Invalid run")),
+                }
+                input = &input[digits + 2..];
+            }
+            if data.len() - start != width as usize {
+                return Err(invalid_data("Row is shorter than grid width"));
+            }
+        }
+        Ok(Grid { data, width: width as usize, height: height as usize })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_binary_round_trip(grid: Grid<u16>) {
+            let mut buf = Vec::new();
+            grid.write_binary(&mut buf).unwrap();
+            assert_eq!(grid, Grid::read_binary(&buf[..]).unwrap());
+        }
+
+        #[test]
+        fn check_binary_round_trip_runs(grid: Grid<bool>) {
+            let mut buf = Vec::new();
+            grid.write_binary(&mut buf).unwrap();
+            assert_eq!(grid, Grid::read_binary(&buf[..]).unwrap());
+        }
+
+        #[test]
+        fn check_rle_round_trip(grid: Grid<u8>) {
+            let grid = grid.map(|b| if *b == b'\n' { b'.' } else { *b });
+            let mut buf = Vec::new();
+            grid.write_rle(&mut buf).unwrap();
+            assert_eq!(grid, Grid::read_rle(&buf[..]).unwrap());
+        }
+
+        #[test]
+        fn check_varint(x: u64) {
+            let mut buf = Vec::new();
+            write_varint(x, &mut buf);
+            let mut input = &buf[..];
+            assert_eq!(Some(x), read_varint(&mut input));
+            assert!(input.is_empty());
+        }
+    }
+
+    #[test]
+    fn rle_text() {
+        let grid = Grid::new(b"..##.11111".to_vec(), 5, 2);
+        let mut buf = Vec::new();
+        grid.write_rle(&mut buf).unwrap();
+        assert_eq!("5x2\n2*.2*#1*.\n5*1\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(Grid::read_rle(&b"2x1\n3*.\n"[..]).is_err());
+        assert!(Grid::read_rle(&b"2x2\n2*.\n"[..]).is_err());
+        assert!(Grid::read_rle(&b"2x1\n2.\n"[..]).is_err());
+        assert!(Grid::new(b"a\n".to_vec(), 2, 1).write_rle(Vec::new()).is_err());
+
+        let mut buf = Vec::new();
+        Grid::new(vec![1u32, 1, 2, 3], 2, 2).write_binary(&mut buf).unwrap();
+        assert!(Grid::<u32>::read_binary(&buf[..buf.len() - 1]).is_err());
+        assert!(Grid::<u32>::read_binary(&buf[1..]).is_err());
+        assert!(Grid::<u64>::read_binary(&buf[..]).is_err());
+    }
+
+    #[test]
+    fn huge_header_on_tiny_body() {
+        let err = Grid::read_rle(&b"1000000000x1000000\n1*.\n"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let mut buf = MAGIC.to_vec();
+        buf.push(VERSION);
+        (1u64 << 31).encode(&mut buf);
+        (1u64 << 31).encode(&mut buf);
+        write_varint(1, &mut buf);
+        0u8.encode(&mut buf);
+        assert_eq!(io::ErrorKind::InvalidData, Grid::<u8>::read_binary(&buf[..]).unwrap_err().kind());
+        // The same dimensions with 8-byte cells need more than `isize::MAX` bytes.
+        let err = Grid::<u64>::read_binary(&buf[..]).unwrap_err();
+        assert_eq!("Grid dimensions are too large", err.to_string());
+    }
+}
@@ -0,0 +1,211 @@
+use alloc::{string::{String, ToString}, vec::Vec};
+use core::fmt::Display;
+
+use crate::{Grid, GridRead, Offset, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    Down,
+    Up,
+}
+
+/// A way of numbering the cells of a grid. [`Position`] always counts from zero at the top left with y increasing downwards,
+/// which is [`Convention::SCREEN`], but puzzles sometimes count from one, or put the origin at the bottom left with y increasing upwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Convention {
+    pub y_axis: YAxis,
+    /// The coordinate of the first row and column, usually 0 or 1.
+    pub base: usize,
+}
+
+impl Convention {
+    pub const SCREEN: Convention = Convention { y_axis: YAxis::Down, base: 0 };
+    pub const CARTESIAN: Convention = Convention { y_axis: YAxis::Up, base: 0 };
+
+    pub const fn new(y_axis: YAxis, base: usize) -> Self {
+        Convention { y_axis, base }
+    }
+
+    /// The position of the cell at coordinates `(x, y)` in this convention, in a grid of the given height.
+    /// Returns `None` for coordinates before the first row or column, or above the top row when y points up.
+    pub fn position(self, x: usize, y: usize, height: usize) -> Option<Position> {
+        let x = x.checked_sub(self.base)?;
+        let y = y.checked_sub(self.base)?;
+        let y = match self.y_axis {
+            YAxis::Down => y,
+            YAxis::Up => height.checked_sub(1)?.checked_sub(y)?,
+        };
+        Some(Position::new(x,y))
+    }
+
+    /// The coordinates of `pos` in this convention, in a grid of the given height. Panics if y points up and `pos` is below the last row.
+    pub fn coords(self, pos: &Position, height: usize) -> (usize, usize) {
+        let y = match self.y_axis {
+            YAxis::Down => pos.y(),
+            YAxis::Up => height.checked_sub(pos.y() + 1).unwrap_or_else(|| panic!("{pos} is outside a grid of height {height}")),
+        };
+        (pos.x() + self.base, y + self.base)
+    }
+
+    /// Converts a displacement in this convention to an [`Offset`]. Only the direction of the y axis matters here.
+    pub fn offset(self, x: isize, y: isize) -> Offset {
+        match self.y_axis {
+            YAxis::Down => Offset::new(x,y),
+            YAxis::Up => Offset::new(x,-y),
+        }
+    }
+}
+
+impl Default for Convention {
+    fn default() -> Self {
+        Convention::SCREEN
+    }
+}
+
+/// Renders a grid with column numbers stacked above it and row numbers down its left side, optionally limited to a window around one cell.
+///
+/// ```text
+///             111
+///   0123456789012
+/// 0 .....#.......
+/// 1 ...#.........
+/// ```
+pub struct Labeled<'a, G> {
+    grid: &'a G,
+    convention: Convention,
+    focus: Option<(Position, usize)>,
+}
+
+impl <'a, G: GridRead> Labeled<'a, G> {
+    pub fn new(grid: &'a G) -> Self {
+        Labeled { grid, convention: Convention::SCREEN, focus: None }
+    }
+
+    /// Numbers the rows and columns according to `convention` instead of as [`Position`]s.
+    pub fn convention(mut self, convention: Convention) -> Self {
+        self.convention = convention;
+        self
+    }
+
+    /// Only renders the cells at most `radius` rows and columns away from `focus`.
+    pub fn focus(mut self, focus: &Position, radius: usize) -> Self {
+        self.focus = Some((focus.clone(), radius));
+        self
+    }
+
+    fn window(&self) -> (core::ops::Range<usize>, core::ops::Range<usize>) {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        match &self.focus {
+            None => (0..width, 0..height),
+            Some((focus, radius)) => (
+                focus.x().saturating_sub(*radius)..width.min(focus.x().saturating_add(*radius + 1)),
+                focus.y().saturating_sub(*radius)..height.min(focus.y().saturating_add(*radius + 1)),
+            ),
+        }
+    }
+}
+
+impl <G: GridRead> Display for Labeled<'_, G>
+where
+    G::Cell: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (xs, ys) = self.window();
+        if xs.is_empty() || ys.is_empty() {
+            return Ok(());
+        }
+        let height = self.grid.height();
+        let rows: Vec<Vec<String>> = ys.clone()
+            .map(|y| xs.clone().map(|x| self.grid.get(&Position::new(x,y)).unwrap().to_string()).collect())
+            .collect();
+        let cell_width = rows.iter().flatten().map(|cell| cell.chars().count()).max().unwrap_or(0).max(1);
+        let column_labels: Vec<String> = xs.map(|x| self.convention.coords(&Position::new(x,0), height).0.to_string()).collect();
+        let row_labels: Vec<String> = ys.map(|y| self.convention.coords(&Position::new(0,y), height).1.to_string()).collect();
+        let ruler_height = column_labels.iter().map(String::len).max().unwrap();
+        let label_width = row_labels.iter().map(String::len).max().unwrap();
+
+        // Column numbers are written vertically, one digit per line, aligned at the bottom
+        for line in 0..ruler_height {
+            write!(f, "{:label_width$} ", "")?;
+            for label in column_labels.iter() {
+                let digit = (line + label.len()).checked_sub(ruler_height).map_or(' ', |i| label.as_bytes()[i] as char);
+                write!(f, "{digit:>cell_width$}")?;
+            }
+            writeln!(f)?;
+        }
+        for (i, (label, row)) in row_labels.iter().zip(rows.iter()).enumerate() {
+            write!(f, "{label:>label_width$} ")?;
+            for cell in row {
+                write!(f, "{cell:>cell_width$}")?;
+            }
+            if i < rows.len() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl <T> Grid<T> {
+    /// Renders the grid with row and column numbers. See [`Labeled`].
+    pub fn labeled(&self) -> Labeled<'_, Grid<T>> {
+        Labeled::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_round_trip(pos: Position, height in 1usize..1000, y_up: bool, base in 0usize..2) {
+            let pos = Position::new(pos.x() % 1000, pos.y() % height);
+            let convention = Convention::new(if y_up { YAxis::Up } else { YAxis::Down }, base);
+            let (x, y) = convention.coords(&pos, height);
+            assert_eq!(Some(pos), convention.position(x, y, height));
+        }
+    }
+
+    #[test]
+    fn conventions() {
+        let convention = Convention::new(YAxis::Up, 1);
+        assert_eq!(Some(Position::new(0,4)), convention.position(1, 1, 5));
+        assert_eq!(Some(Position::new(2,0)), convention.position(3, 5, 5));
+        assert_eq!(None, convention.position(1, 6, 5));
+        assert_eq!(None, convention.position(0, 1, 5));
+        assert_eq!(Offset::new(0,-1), convention.offset(0, 1));
+        assert_eq!((1, 1), convention.coords(&Position::new(0,4), 5));
+    }
+
+    fn sample() -> Grid<char> {
+        let rows = ["..........#.", "...#........", "............"];
+        rows.iter().map(|row| row.chars()).collect()
+    }
+
+    #[test]
+    fn rulers() {
+        let expected = [
+            "            11",
+            "  012345678901",
+            "0 ..........#.",
+            "1 ...#........",
+            "2 ............",
+        ];
+        assert_eq!(expected.join("\n"), sample().labeled().to_string());
+    }
+
+    #[test]
+    fn focus_window() {
+        let expected = [
+            "    111",
+            "  89012",
+            "3 ...#.",
+            "2 .....",
+            "1 .....",
+        ];
+        let rendered = sample().labeled().convention(Convention::new(YAxis::Up, 1)).focus(&Position::new(9,0), 2).to_string();
+        assert_eq!(expected.join("\n"), rendered);
+    }
+}
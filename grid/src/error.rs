@@ -0,0 +1,27 @@
+use alloc::string::String;
+use core::fmt::Display;
+
+use crate::{Cardinality, Position};
+
+/// Errors from building a grid out of text or rows of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Row `row` has `found` cells, but earlier rows have `expected`.
+    RaggedRow { row: usize, expected: usize, found: usize },
+    /// The cell at `pos` could not be parsed from `c`.
+    UnexpectedChar { pos: Position, c: char },
+    /// The characters in `markers` appeared `found` times in total, which `expected` does not allow.
+    MarkerCount { markers: String, expected: Cardinality, found: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::RaggedRow { row, expected, found } => write!(f, "Row {row} has {found} cells, expected {expected}"),
+            ParseError::UnexpectedChar { pos, c } => write!(f, "Unexpected character {c:?} at {pos}"),
+            ParseError::MarkerCount { markers, expected, found } => write!(f, "Expected {expected} of {markers:?}, found {found}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,347 @@
+//! Colored terminal rendering of grids using ANSI escape codes.
+//!
+//! [`Heatmap`] colors numeric cells along a gradient, and [`Categorical`] gives each distinct value its own color.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::Grid;
+
+const RESET: &str = "\x1b[0m";
+
+/// Gradient stops from dark purple through teal to yellow, evenly spaced.
+const GRADIENT: [(u8, u8, u8); 5] = [(68, 1, 84), (59, 82, 139), (33, 145, 140), (94, 201, 98), (253, 231, 37)];
+
+/// Values that a [`Heatmap`] can place on its gradient.
+pub trait HeatValue {
+    /// The value as a number, or `None` for cells that should be left uncolored, such as unreached cells in a distance map.
+    fn heat(&self) -> Option<f64>;
+}
+
+macro_rules! impl_heat_value {
+    ($($t:ty),*) => {
+        $(
+            impl HeatValue for $t {
+                fn heat(&self) -> Option<f64> {
+                    Some(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_heat_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32);
+
+impl HeatValue for f64 {
+    fn heat(&self) -> Option<f64> {
+        Some(*self).filter(|x| !x.is_nan())
+    }
+}
+
+impl <T: HeatValue> HeatValue for Option<T> {
+    fn heat(&self) -> Option<f64> {
+        self.as_ref()?.heat()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// The 6x6x6 color cube of 256-color terminals.
+    #[default]
+    Ansi256,
+    /// 24-bit color, where the terminal supports it.
+    TrueColor,
+}
+
+impl ColorMode {
+    fn background(self, (r, g, b): (u8, u8, u8)) -> String {
+        match self {
+            ColorMode::TrueColor => format!("\x1b[48;2;{r};{g};{b}m"),
+            ColorMode::Ansi256 => {
+                let level = |c: u8| (c as u16 * 5 + 127) / 255;
+                format!("\x1b[48;5;{}m", 16 + 36 * level(r) + 6 * level(g) + level(b))
+            },
+        }
+    }
+
+    /// Sets a background color, with black or white text, whichever is more readable on it.
+    fn cell(self, color: (u8, u8, u8)) -> String {
+        let (r, g, b) = color;
+        let luma = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+        let foreground = if luma > 128.0 { "\x1b[30m" } else { "\x1b[97m" };
+        format!("{}{foreground}", self.background(color))
+    }
+}
+
+/// How values are spread along the gradient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scale {
+    /// Evenly between the smallest and largest values.
+    #[default]
+    Linear,
+    /// Logarithmically above the smallest value, so that differences among small values stand out.
+    Log,
+    /// By rank among the distinct values, so that each gets an equal share of the gradient however they are distributed.
+    Quantile,
+}
+
+/// Maps values to positions on the gradient between 0 and 1.
+struct Scaler {
+    scale: Scale,
+    min: f64,
+    max: f64,
+    /// Distinct values in ascending order, for quantiles.
+    sorted: Vec<f64>,
+}
+
+impl Scaler {
+    fn new<I: Iterator<Item = f64>>(scale: Scale, values: I) -> Option<Scaler> {
+        let mut sorted: Vec<f64> = values.collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted.dedup();
+        Some(Scaler { scale, min: *sorted.first()?, max: *sorted.last()?, sorted })
+    }
+
+    fn position(&self, value: f64) -> f64 {
+        if self.max == self.min {
+            return 0.0;
+        }
+        match self.scale {
+            Scale::Linear => (value - self.min) / (self.max - self.min),
+            Scale::Log => (value - self.min).ln_1p() / (self.max - self.min).ln_1p(),
+            Scale::Quantile => self.sorted.partition_point(|x| *x < value) as f64 / (self.sorted.len() - 1) as f64,
+        }
+    }
+
+    /// The value found at position `t` on the gradient.
+    fn value_at(&self, t: f64) -> f64 {
+        match self.scale {
+            Scale::Linear => self.min + t * (self.max - self.min),
+            Scale::Log => self.min + (t * (self.max - self.min).ln_1p()).exp_m1(),
+            Scale::Quantile => self.sorted[(t * (self.sorted.len() - 1) as f64).round() as usize],
+        }
+    }
+}
+
+fn gradient(t: f64) -> (u8, u8, u8) {
+    let scaled = t.clamp(0.0, 1.0) * (GRADIENT.len() - 1) as f64;
+    let i = (scaled.floor() as usize).min(GRADIENT.len() - 2);
+    let frac = scaled - i as f64;
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+    let ((r0, g0, b0), (r1, g1, b1)) = (GRADIENT[i], GRADIENT[i + 1]);
+    (lerp(r0, r1), lerp(g0, g1), lerp(b0, b1))
+}
+
+/// Formats a legend value without a fractional part when it is a whole number.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// Renders a numeric grid as blocks of color, two columns per cell so that they come out roughly square.
+/// Cells without a value are left blank. Created with [`Grid::heatmap`].
+pub struct Heatmap<'a, T> {
+    grid: &'a Grid<T>,
+    scale: Scale,
+    mode: ColorMode,
+    legend: bool,
+}
+
+impl <T: HeatValue> Heatmap<'_, T> {
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn colors(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a line below the grid showing the values at evenly spaced points along the gradient.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+}
+
+impl <T: HeatValue> Display for Heatmap<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(scaler) = Scaler::new(self.scale, self.grid.iter().filter_map(HeatValue::heat)) else {
+            // Nothing to color, so show the shape of the grid only
+            for _ in 0..self.grid.height {
+                writeln!(f, "{}", "  ".repeat(self.grid.width))?;
+            }
+            return Ok(());
+        };
+        for row in self.grid.data.chunks(self.grid.width.max(1)) {
+            for cell in row {
+                match cell.heat() {
+                    Some(value) => write!(f, "{}  ", self.mode.background(gradient(scaler.position(value))))?,
+                    None => write!(f, "{RESET}  ")?,
+                }
+            }
+            writeln!(f, "{RESET}")?;
+        }
+        if self.legend {
+            const STEPS: usize = 5;
+            for step in 0..STEPS {
+                let t = step as f64 / (STEPS - 1) as f64;
+                write!(f, "{}  {RESET} {} ", self.mode.background(gradient(t)), format_value(scaler.value_at(t)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders each cell as its own text on a background color that identifies its value, with distinct values getting distinct colors.
+/// Created with [`Grid::categorical`].
+pub struct Categorical<'a, T> {
+    grid: &'a Grid<T>,
+    mode: ColorMode,
+    legend: bool,
+}
+
+impl <T: Ord + Display> Categorical<'_, T> {
+    pub fn colors(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a line below the grid listing each value next to its color.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Assigns colors to the distinct values in sorted order, stepping the hue by the golden angle so that neighbors differ strongly.
+    fn palette(&self) -> BTreeMap<&T, String> {
+        let mut values: Vec<&T> = self.grid.iter().collect();
+        values.sort();
+        values.dedup();
+        values.into_iter().enumerate()
+            .map(|(i, value)| {
+                let hue = (i as f64 * 137.507_764) % 360.0;
+                let lightness = if i % 2 == 0 { 0.55 } else { 0.40 };
+                (value, self.mode.cell(hsl(hue, 0.65, lightness)))
+            })
+            .collect()
+    }
+}
+
+/// Converts a hue in degrees, with saturation and lightness between 0 and 1, to RGB.
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+impl <T: Ord + Display> Display for Categorical<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let palette = self.palette();
+        for row in self.grid.data.chunks(self.grid.width.max(1)) {
+            for cell in row {
+                write!(f, "{}{cell}", palette[cell])?;
+            }
+            writeln!(f, "{RESET}")?;
+        }
+        if self.legend {
+            for (value, color) in palette.iter() {
+                write!(f, "{color} {value} {RESET} ")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl <T: HeatValue> Grid<T> {
+    /// Renders the grid as a heatmap. See [`Heatmap`].
+    pub fn heatmap(&self) -> Heatmap<'_, T> {
+        Heatmap { grid: self, scale: Scale::default(), mode: ColorMode::default(), legend: false }
+    }
+}
+
+impl <T: Ord + Display> Grid<T> {
+    /// Renders the grid with a color for each distinct value. See [`Categorical`].
+    pub fn categorical(&self) -> Categorical<'_, T> {
+        Categorical { grid: self, mode: ColorMode::default(), legend: false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_scale_bounds(values: Vec<u16>, log: bool, quantile: bool) {
+            let scale = if quantile { Scale::Quantile } else if log { Scale::Log } else { Scale::Linear };
+            if let Some(scaler) = Scaler::new(scale, values.iter().map(|x| *x as f64)) {
+                for value in values.iter() {
+                    let t = scaler.position(*value as f64);
+                    assert!((0.0..=1.0).contains(&t), "{t}");
+                }
+                assert_eq!(scaler.min, scaler.value_at(0.0));
+                assert!((scaler.max - scaler.value_at(1.0)).abs() < 1e-6);
+            }
+        }
+    }
+
+    fn strip_escapes(s: &str) -> String {
+        let mut r = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| *c == 'm');
+            } else {
+                r.push(c);
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn scales() {
+        let scaler = Scaler::new(Scale::Quantile, [1.0, 10.0, 1000.0].into_iter()).unwrap();
+        assert_eq!(0.5, scaler.position(10.0));
+        let scaler = Scaler::new(Scale::Linear, [0.0, 10.0].into_iter()).unwrap();
+        assert_eq!(0.25, scaler.position(2.5));
+        let scaler = Scaler::new(Scale::Log, [0.0, 99.0].into_iter()).unwrap();
+        assert_eq!(0.5, scaler.position(9.0));
+    }
+
+    #[test]
+    fn heatmap() {
+        let grid = Grid::new(vec![Some(0u32), Some(4), None, Some(8)], 2, 2);
+        let rendered = grid.heatmap().colors(ColorMode::TrueColor).legend(true).to_string();
+        assert!(rendered.starts_with("\x1b[48;2;68;1;84m  \x1b[48;2;33;145;140m  \x1b[0m\n"));
+        assert_eq!("    \n    \n   0    2    4    6    8 \n", strip_escapes(&rendered));
+        assert_eq!(2, grid.heatmap().to_string().lines().filter(|line| line.contains("\x1b[48;5;")).count());
+    }
+
+    #[test]
+    fn categories() {
+        let grid: Grid<char> = ["AAB", "CAB"].iter().map(|row| row.chars()).collect();
+        let rendered = grid.categorical().legend(true).to_string();
+        assert_eq!("AAB\nCAB\n A   B   C  \n", strip_escapes(&rendered));
+        let categorical = grid.categorical();
+        let palette = categorical.palette();
+        assert_eq!(3, palette.values().collect::<std::collections::BTreeSet<_>>().len());
+    }
+}
@@ -0,0 +1,418 @@
+//! Hexagonal coordinates and grids.
+//!
+//! Hexes use axial coordinates `(q, r)` for pointy-topped hexagons: `q` increases to the east and `r` increases to the south-east,
+//! so that rows of constant `r` run horizontally like the rows of a [`Grid`]. The implied third cube coordinate is `s = -q - r`.
+
+use alloc::{vec, vec::Vec};
+use core::{fmt::Display, ops::{Add, Index, IndexMut, Mul, Sub}};
+
+use crate::{Grid, ParseError, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    q: isize,
+    r: isize,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: isize, r: isize) -> Self {
+        Hex { q, r }
+    }
+
+    /// Panics if the cube coordinates do not sum to zero.
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Self {
+        if q + r + s != 0 {
+            panic!("Invalid cube coordinates ({q},{r},{s})");
+        }
+        Hex { q, r }
+    }
+
+    pub fn q(&self) -> isize {
+        self.q
+    }
+
+    pub fn r(&self) -> isize {
+        self.r
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn neighbor(&self, dir: HexDirection) -> Hex {
+        *self + dir.to_hex()
+    }
+
+    /// The six adjacent hexes, in the order of [`HexDirection::ALL`].
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|dir| self.neighbor(dir))
+    }
+
+    /// The number of steps from the origin to this hex.
+    pub fn length(&self) -> usize {
+        (self.q.unsigned_abs() + self.r.unsigned_abs() + self.s().unsigned_abs()) / 2
+    }
+
+    /// The number of steps between two hexes.
+    pub fn distance(&self, other: &Hex) -> usize {
+        (*self - *other).length()
+    }
+
+    /// Rotates this hex 60 degrees clockwise (as drawn on screen) around the origin.
+    pub fn rotate_right(&self) -> Hex {
+        Hex::from_cube(-self.r, -self.s(), -self.q)
+    }
+
+    /// Rotates this hex 60 degrees counter-clockwise (as drawn on screen) around the origin.
+    pub fn rotate_left(&self) -> Hex {
+        Hex::from_cube(-self.s(), -self.q, -self.r)
+    }
+
+    /// Rotates this hex around `center` by `turns` sixths of a full turn. Positive values rotate clockwise.
+    pub fn rotate_around(&self, center: &Hex, turns: isize) -> Hex {
+        let mut rel = *self - *center;
+        for _ in 0..turns.rem_euclid(6) {
+            rel = rel.rotate_right();
+        }
+        *center + rel
+    }
+
+    /// All hexes at exactly `radius` steps from this one, walking clockwise from the east-most hex.
+    /// A radius of zero yields just this hex.
+    pub fn ring(&self, radius: usize) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let mut r = Vec::with_capacity(6 * radius);
+        let mut hex = *self + HexDirection::East.to_hex() * radius as isize;
+        // Starting from the east corner, each side of the ring heads in the direction 120 degrees clockwise from the corner's direction
+        for dir in [HexDirection::SouthWest, HexDirection::West, HexDirection::NorthWest, HexDirection::NorthEast, HexDirection::East, HexDirection::SouthEast] {
+            for _ in 0..radius {
+                r.push(hex);
+                hex = hex.neighbor(dir);
+            }
+        }
+        debug_assert_eq!(*self + HexDirection::East.to_hex() * radius as isize, hex);
+        r
+    }
+
+    /// The hexes along the straight line from this hex to `other`, inclusive of both ends. Consecutive hexes are adjacent.
+    pub fn line_to(&self, other: &Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        // Nudging the endpoints keeps lines along hex edges from landing exactly between two hexes, which would round inconsistently
+        let (q1, r1, s1) = (self.q as f64 + 1e-6, self.r as f64 + 2e-6, self.s() as f64 - 3e-6);
+        let (q2, r2, s2) = (other.q as f64 + 1e-6, other.r as f64 + 2e-6, other.s() as f64 - 3e-6);
+        (0..=n).map(|i| {
+            let t = i as f64 / n as f64;
+            cube_round(q1 + (q2 - q1) * t, r1 + (r2 - r1) * t, s1 + (s2 - s1) * t)
+        })
+        .collect()
+    }
+}
+
+/// Rounds fractional cube coordinates to the nearest hex, fixing up whichever component had the largest rounding error.
+fn cube_round(q: f64, r: f64, s: f64) -> Hex {
+    let (mut rq, mut rr, rs) = (round(q), round(r), round(s));
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    Hex::new(rq as isize, rr as isize)
+}
+
+/// Rounds half away from zero, like `f64::round`, which is unavailable without `std`.
+fn round(x: f64) -> f64 {
+    let truncated = x as i64 as f64;
+    if (x - truncated).abs() >= 0.5 { truncated + x.signum() } else { truncated }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Hex;
+    fn mul(self, rhs: isize) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{},{}]", self.q, self.r)
+    }
+}
+
+/// The six directions between adjacent pointy-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDirection {
+    East, NorthEast, NorthWest, West, SouthWest, SouthEast
+}
+
+impl HexDirection {
+    /// All directions, counter-clockwise starting from `East`.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East, HexDirection::NorthEast, HexDirection::NorthWest,
+        HexDirection::West, HexDirection::SouthWest, HexDirection::SouthEast,
+    ];
+
+    /// The offset to the adjacent hex in this direction.
+    pub fn to_hex(self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::NorthEast => Hex::new(1, -1),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::SouthEast => Hex::new(0, 1),
+        }
+    }
+}
+
+/// How hex rows are arranged when stored or drawn as a rectangle of rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OffsetLayout {
+    /// Odd rows are shifted right by half a hex.
+    OddRows,
+    /// Even rows are shifted right by half a hex.
+    EvenRows,
+}
+
+impl OffsetLayout {
+    /// Converts a hex to its (column, row) in this layout, if both are non-negative.
+    pub fn to_position(self, hex: &Hex) -> Option<Position> {
+        let parity = hex.r & 1;
+        let col = match self {
+            OffsetLayout::OddRows => hex.q + (hex.r - parity) / 2,
+            OffsetLayout::EvenRows => hex.q + (hex.r + parity) / 2,
+        };
+        Some(Position::new(col.try_into().ok()?, hex.r.try_into().ok()?))
+    }
+
+    pub fn to_hex(self, pos: &Position) -> Hex {
+        let (col, row) = (pos.0 as isize, pos.1 as isize);
+        let parity = row & 1;
+        let q = match self {
+            OffsetLayout::OddRows => col - (row - parity) / 2,
+            OffsetLayout::EvenRows => col - (row + parity) / 2,
+        };
+        Hex::new(q, row)
+    }
+
+    fn is_shifted(self, row: usize) -> bool {
+        match self {
+            OffsetLayout::OddRows => !row.is_multiple_of(2),
+            OffsetLayout::EvenRows => row.is_multiple_of(2),
+        }
+    }
+}
+
+/// A rectangular region of hexes stored densely by row, with the rows arranged according to an [`OffsetLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    cells: Grid<T>,
+    layout: OffsetLayout,
+}
+
+impl <T> HexGrid<T> {
+    /// Wraps a grid whose columns and rows are offset coordinates in `layout`.
+    pub fn new(cells: Grid<T>, layout: OffsetLayout) -> Self {
+        HexGrid { cells, layout }
+    }
+
+    /// Parses rows of single-character cells, ignoring whitespace. Shifted rows are usually indented by one space with a space between cells:
+    ///
+    /// ```text
+    /// a b c
+    ///  d e f
+    /// g h i
+    /// ```
+    pub fn parse<F>(input: &str, layout: OffsetLayout, mut f: F) -> Result<HexGrid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut data = Vec::new();
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        for (row, line) in input.lines().enumerate() {
+            let start = data.len();
+            for (col, c) in line.chars().filter(|c| !c.is_whitespace()).enumerate() {
+                data.push(f(c).ok_or(ParseError::UnexpectedChar { pos: Position::new(col, row), c })?);
+            }
+            let found = data.len() - start;
+            match width {
+                Some(expected) if expected != found => return Err(ParseError::RaggedRow { row, expected, found }),
+                _ => width = Some(found),
+            }
+            height += 1;
+        }
+        Ok(HexGrid { cells: Grid::new(data, width.unwrap_or(0), height), layout })
+    }
+
+    pub fn layout(&self) -> OffsetLayout {
+        self.layout
+    }
+
+    /// The underlying storage, indexed by offset coordinates.
+    pub fn cells(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn contains(&self, hex: &Hex) -> bool {
+        self.layout.to_position(hex).is_some_and(|pos| self.cells.in_bounds(&pos))
+    }
+
+    pub fn get(&self, hex: &Hex) -> Option<&T> {
+        self.cells.get(&self.layout.to_position(hex)?)
+    }
+
+    pub fn get_mut(&mut self, hex: &Hex) -> Option<&mut T> {
+        self.cells.get_mut(&self.layout.to_position(hex)?)
+    }
+
+    /// All hexes in the grid, row by row.
+    pub fn hexes(&self) -> impl Iterator<Item = Hex> + '_ {
+        (0..self.height()).flat_map(move |y| (0..self.width()).map(move |x| self.layout.to_hex(&Position::new(x,y))))
+    }
+
+    /// The neighbors of `hex` that lie within the grid.
+    pub fn neighbors(&self, hex: &Hex) -> impl Iterator<Item = Hex> + '_ {
+        hex.neighbors().into_iter().filter(|n| self.contains(n))
+    }
+}
+
+impl <T> Index<&Hex> for HexGrid<T> {
+    type Output = T;
+
+    fn index(&self, hex: &Hex) -> &Self::Output {
+        self.get(hex).unwrap_or_else(|| panic!("Out of bounds hex: {hex}"))
+    }
+}
+
+impl <T> IndexMut<&Hex> for HexGrid<T> {
+    fn index_mut(&mut self, hex: &Hex) -> &mut Self::Output {
+        self.get_mut(hex).unwrap_or_else(|| panic!("Out of bounds hex: {hex}"))
+    }
+}
+
+/// Draws the grid in the layout accepted by [`HexGrid::parse`].
+impl <T: Display> Display for HexGrid<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for y in 0..self.height() {
+            if self.layout.is_shifted(y) {
+                write!(f, " ")?;
+            }
+            for x in 0..self.width() {
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", self.cells[&Position::new(x,y)])?;
+            }
+            if y < self.height() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn hex() -> impl Strategy<Value = Hex> {
+        (-1000isize..1000, -1000isize..1000).prop_map(|(q, r)| Hex::new(q, r))
+    }
+
+    proptest! {
+        #[test]
+        fn check_rotation(a in hex(), center in hex()) {
+            assert_eq!(a, a.rotate_right().rotate_left());
+            assert_eq!(a.length(), a.rotate_right().length());
+            assert_eq!(a, a.rotate_around(&center, 6));
+            assert_eq!(a.rotate_around(&center, -1), a.rotate_around(&center, 5));
+            assert_eq!(a.distance(&center), a.rotate_around(&center, 2).distance(&center));
+        }
+
+        #[test]
+        fn check_line(a in hex(), b in hex()) {
+            let line = a.line_to(&b);
+            assert_eq!(a.distance(&b) + 1, line.len());
+            assert_eq!(Some(&a), line.first());
+            assert_eq!(Some(&b), line.last());
+            assert!(line.windows(2).all(|w| w[0].distance(&w[1]) == 1));
+        }
+
+        #[test]
+        fn check_ring(center in hex(), radius in 0usize..20) {
+            let ring = center.ring(radius);
+            assert_eq!(core::cmp::max(1, 6 * radius), ring.len());
+            assert!(ring.iter().all(|hex| hex.distance(&center) == radius));
+        }
+
+        #[test]
+        fn check_offset_round_trip(x in 0usize..1000, y in 0usize..1000) {
+            for layout in [OffsetLayout::OddRows, OffsetLayout::EvenRows] {
+                let pos = Position::new(x,y);
+                assert_eq!(Some(pos.clone()), layout.to_position(&layout.to_hex(&pos)));
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_are_adjacent() {
+        let hex = Hex::new(3, -2);
+        assert!(hex.neighbors().iter().all(|n| n.distance(&hex) == 1));
+        assert_eq!(hex, hex.neighbor(HexDirection::NorthWest).neighbor(HexDirection::SouthEast));
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let input = "a b c\n d e f\ng h i";
+        let grid = HexGrid::parse(input, OffsetLayout::OddRows, Some).unwrap();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(input, grid.to_string());
+
+        // 'e' is surrounded by every other letter except 'a' and 'g', which are too far left
+        let e = OffsetLayout::OddRows.to_hex(&Position::new(1,1));
+        assert_eq!('e', grid[&e]);
+        let mut neighbors: Vec<char> = grid.neighbors(&e).map(|n| grid[&n]).collect();
+        neighbors.sort();
+        assert_eq!(vec!['b', 'c', 'd', 'f', 'h', 'i'], neighbors);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(ParseError::RaggedRow { row: 1, expected: 2, found: 1 }), HexGrid::parse("a b\n c", OffsetLayout::OddRows, Some));
+        assert_eq!(
+            Err(ParseError::UnexpectedChar { pos: Position::new(1,0), c: 'x' }),
+            HexGrid::parse("a x", OffsetLayout::OddRows, |c| (c != 'x').then_some(c)),
+        );
+    }
+}
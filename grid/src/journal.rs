@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use crate::{GridRead, GridWrite, Position};
+
+/// A point in a [`Journaled`] grid's history that it can be rolled back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checkpoint(usize);
+
+/// Wraps a grid and records the previous value of every cell written through it, so that a run of speculative changes can be undone
+/// without cloning the whole grid.
+///
+/// Checkpoints nest: rolling back to one discards every later checkpoint along with the writes made since.
+#[derive(Debug, Clone)]
+pub struct Journaled<G: GridWrite> {
+    grid: G,
+    journal: Vec<(Position, G::Cell)>,
+}
+
+impl <G: GridWrite> Journaled<G>
+where
+    G::Cell: Clone,
+{
+    pub fn new(grid: G) -> Self {
+        Journaled { grid, journal: Vec::new() }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.journal.len())
+    }
+
+    /// Restores every cell written since `checkpoint`, most recent first. Panics if an earlier rollback already discarded `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        assert!(checkpoint.0 <= self.journal.len(), "Checkpoint has already been rolled back");
+        for (pos, old) in self.journal.drain(checkpoint.0..).rev() {
+            *self.grid.get_mut(&pos).expect("journaled positions are in bounds") = old;
+        }
+    }
+
+    /// Keeps every write made so far and forgets how to undo them, invalidating all checkpoints.
+    pub fn commit(&mut self) {
+        self.journal.clear();
+    }
+
+    /// The number of writes that a rollback to the start would undo.
+    pub fn journal_len(&self) -> usize {
+        self.journal.len()
+    }
+
+    pub fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Unwraps the grid, keeping every write made so far.
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+}
+
+impl <G: GridWrite> GridRead for Journaled<G> {
+    type Cell = G::Cell;
+
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn get(&self, pos: &Position) -> Option<&G::Cell> {
+        self.grid.get(pos)
+    }
+}
+
+/// The old value of a cell is recorded as soon as it is borrowed mutably, whether or not it is then changed.
+impl <G: GridWrite> GridWrite for Journaled<G>
+where
+    G::Cell: Clone,
+{
+    fn get_mut(&mut self, pos: &Position) -> Option<&mut G::Cell> {
+        let cell = self.grid.get_mut(pos)?;
+        self.journal.push((pos.clone(), cell.clone()));
+        Some(cell)
+    }
+}
+
+impl <G: GridWrite> Index<&Position> for Journaled<G> {
+    type Output = G::Cell;
+
+    fn index(&self, pos: &Position) -> &Self::Output {
+        self.grid.get(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_rollback_restores(grid: Grid<u8>, writes in prop::collection::vec((0usize..100, 0usize..100, any::<u8>()), 0..50), split in 0usize..50) {
+            let mut journaled = Journaled::new(grid.clone());
+            let split = split.min(writes.len());
+            for (x, y, value) in writes[..split].iter() {
+                journaled.set(&Position::new(*x, *y), *value);
+            }
+            let before = journaled.grid().clone();
+            let checkpoint = journaled.checkpoint();
+            for (x, y, value) in writes[split..].iter() {
+                journaled.set(&Position::new(*x, *y), *value);
+            }
+            journaled.rollback(checkpoint);
+            assert_eq!(&before, journaled.grid());
+            journaled.rollback(Checkpoint(0));
+            assert_eq!(grid, journaled.into_inner());
+        }
+    }
+
+    #[test]
+    fn nested_checkpoints() {
+        let mut grid = Journaled::new(Grid::new(b"....".to_vec(), 2, 2));
+        let outer = grid.checkpoint();
+        grid.set(&Position::new(0,0), b'#');
+        let inner = grid.checkpoint();
+        *grid.get_mut(&Position::new(1,1)).unwrap() = b'#';
+        grid.set(&Position::new(0,0), b'O');
+        assert_eq!(None, grid.set(&Position::new(2,0), b'#'));
+        assert_eq!(3, grid.journal_len());
+
+        grid.rollback(inner);
+        assert_eq!(b'#', grid[&Position::new(0,0)]);
+        assert_eq!(b'.', grid[&Position::new(1,1)]);
+        grid.rollback(outer);
+        assert_eq!(&Grid::new(b"....".to_vec(), 2, 2), grid.grid());
+    }
+
+    #[test]
+    #[should_panic]
+    fn stale_checkpoint() {
+        let mut grid = Journaled::new(Grid::new(vec![0; 4], 2, 2));
+        grid.set(&Position::new(0,0), 1);
+        let checkpoint = grid.checkpoint();
+        grid.rollback(Checkpoint(0));
+        grid.rollback(checkpoint);
+    }
+}
@@ -0,0 +1,175 @@
+/// Declares a grid with several layers of cells stored side by side, one `Vec` per layer, sharing a single width and height.
+///
+/// The declaration names the grid type, then the types used for a shared and a mutable borrow of one cell across every layer:
+///
+/// ```
+/// grid::layered_grid! {
+///     #[derive(Debug, Clone)]
+///     pub struct Board, BoardCell, BoardCellMut {
+///         terrain: u8,
+///         visited: bool,
+///     }
+/// }
+///
+/// let mut board = Board::filled(3, 2, b'.', false);
+/// let pos = grid::Position::new(1,1);
+/// let cell = board.get_mut(&pos).unwrap();
+/// *cell.visited = true;
+/// assert_eq!(b'.', *board.at(&pos).terrain);
+/// assert_eq!(1, board.iter().filter(|(_, cell)| *cell.visited).count());
+/// ```
+///
+/// The generated grid has one bounds check per access however many layers it has, an accessor returning each layer as a
+/// [`GridRef`](crate::GridRef), and joint iteration over every layer at once. The layer fields themselves are private to the
+/// declaring module, which may use them directly for whole-layer operations such as `board.visited.fill(false)`.
+#[macro_export]
+macro_rules! layered_grid {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident, $cell:ident, $cell_mut:ident {
+            $($(#[$field_meta:meta])* $field:ident : $ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            width: usize,
+            height: usize,
+            $($(#[$field_meta])* $field: $crate::__private::Vec<$ty>,)+
+        }
+
+        /// Shared references to every layer of one cell.
+        #[derive(Debug, Clone, Copy)]
+        #[allow(dead_code)]
+        $vis struct $cell<'a> {
+            $($vis $field: &'a $ty,)+
+        }
+
+        /// Mutable references to every layer of one cell.
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        $vis struct $cell_mut<'a> {
+            $($vis $field: &'a mut $ty,)+
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Panics if any layer does not hold exactly `width * height` cells.
+            $vis fn new(width: usize, height: usize, $($field: $crate::__private::Vec<$ty>),+) -> Self {
+                $(assert_eq!(width * height, $field.len(), "Layer {} does not match the grid dimensions", stringify!($field));)+
+                $name { width, height, $($field),+ }
+            }
+
+            /// Builds the layers from separate grids. Panics if their dimensions differ.
+            $vis fn from_layers($($field: $crate::Grid<$ty>),+) -> Self {
+                let (width, height) = [$(($field.width(), $field.height())),+][0];
+                $(assert_eq!((width, height), ($field.width(), $field.height()), "Layer {} does not match the grid dimensions", stringify!($field));)+
+                $name { width, height, $($field: $field.into_vec()),+ }
+            }
+
+            /// Fills every cell of each layer with a copy of the given value.
+            $vis fn filled(width: usize, height: usize, $($field: $ty),+) -> Self {
+                $name { width, height, $($field: ::core::iter::repeat_n($field, width * height).collect()),+ }
+            }
+
+            $vis fn width(&self) -> usize {
+                self.width
+            }
+
+            $vis fn height(&self) -> usize {
+                self.height
+            }
+
+            $vis fn in_bounds(&self, pos: &$crate::Position) -> bool {
+                pos.x() < self.width && pos.y() < self.height
+            }
+
+            fn index_of(&self, pos: &$crate::Position) -> Option<usize> {
+                self.in_bounds(pos).then(|| pos.x() + pos.y() * self.width)
+            }
+
+            $vis fn get(&self, pos: &$crate::Position) -> Option<$cell<'_>> {
+                let i = self.index_of(pos)?;
+                Some($cell { $($field: &self.$field[i]),+ })
+            }
+
+            $vis fn get_mut(&mut self, pos: &$crate::Position) -> Option<$cell_mut<'_>> {
+                let i = self.index_of(pos)?;
+                Some($cell_mut { $($field: &mut self.$field[i]),+ })
+            }
+
+            /// Like [`get`](Self::get), but panics if `pos` is out of bounds.
+            $vis fn at(&self, pos: &$crate::Position) -> $cell<'_> {
+                self.get(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+            }
+
+            /// Like [`get_mut`](Self::get_mut), but panics if `pos` is out of bounds.
+            $vis fn at_mut(&mut self, pos: &$crate::Position) -> $cell_mut<'_> {
+                self.get_mut(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+            }
+
+            $(
+                $vis fn $field(&self) -> $crate::GridRef<'_, $ty> {
+                    $crate::GridRef::new(&self.$field, self.width, self.height, self.width)
+                }
+            )+
+
+            /// Every cell across all layers with its position, in row-major order.
+            $vis fn iter(&self) -> impl Iterator<Item = ($crate::Position, $cell<'_>)> {
+                let width = self.width;
+                $(let mut $field = self.$field.iter();)+
+                (0..self.width * self.height).map_while(move |i| {
+                    let cell = $cell { $($field: $field.next()?),+ };
+                    Some(($crate::Position::new(i % width, i / width), cell))
+                })
+            }
+
+            /// Every cell across all layers with its position, in row-major order, borrowed mutably so that any layer can be updated in place.
+            $vis fn iter_mut(&mut self) -> impl Iterator<Item = ($crate::Position, $cell_mut<'_>)> {
+                let width = self.width;
+                $(let mut $field = self.$field.iter_mut();)+
+                (0..self.width * self.height).map_while(move |i| {
+                    let cell = $cell_mut { $($field: $field.next()?),+ };
+                    Some(($crate::Position::new(i % width, i / width), cell))
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Position};
+
+    layered_grid! {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Board, Cell, CellMut {
+            terrain: u8,
+            /// Distance from the start, if reached
+            distance: Option<u32>,
+        }
+    }
+
+    #[test]
+    fn layers() {
+        let mut board = Board::from_layers(Grid::new(b"..#.".to_vec(), 2, 2), Grid::new(vec![None; 4], 2, 2));
+        let start = Position::new(0,0);
+        *board.at_mut(&start).distance = Some(0);
+        assert!(board.get(&Position::new(2,0)).is_none());
+        assert_eq!(b'#', board.terrain()[&Position::new(0,1)]);
+
+        for (pos, cell) in board.iter_mut() {
+            if *cell.terrain == b'.' && cell.distance.is_none() {
+                *cell.distance = Some((pos.x() + pos.y()) as u32);
+            }
+        }
+        let distances: Vec<Option<u32>> = board.iter().map(|(_, cell)| *cell.distance).collect();
+        assert_eq!(vec![Some(0), Some(1), None, Some(2)], distances);
+        assert_eq!(board, Board::new(2, 2, b"..#.".to_vec(), distances));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_layers() {
+        Board::from_layers(Grid::new(b"..".to_vec(), 2, 1), Grid::new(vec![None; 2], 1, 2));
+    }
+}
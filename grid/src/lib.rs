@@ -1,4 +1,50 @@
-use std::{fmt::Display, ops::{Add, Index, IndexMut, Mul, Sub}};
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt::Display, ops::{Add, Index, IndexMut, Mul, Sub}};
+mod array;
+mod builder;
+#[cfg(feature = "std")] mod codec;
+mod coords;
+mod error;
+#[cfg(feature = "std")] mod heatmap;
+mod hex;
+mod journal;
+mod layers;
+mod markers;
+mod path;
+mod rect;
+mod resize;
+#[cfg(feature = "std")] mod shortest;
+mod shape;
+mod simulate;
+mod view;
+mod visibility;
+mod windows;
+pub use array::ArrayGrid;
+pub use builder::GridBuilder;
+#[cfg(feature = "std")] pub use codec::CellCodec;
+pub use coords::{Convention, Labeled, YAxis};
+pub use error::ParseError;
+#[cfg(feature = "std")] pub use heatmap::{Categorical, ColorMode, HeatValue, Heatmap, Scale};
+pub use hex::{Hex, HexDirection, HexGrid, OffsetLayout};
+pub use journal::{Checkpoint, Journaled};
+pub use markers::{Cardinality, Marked, Markers};
+pub use path::{Path, PathStyle, Step};
+pub use rect::Rect;
+pub use resize::Padding;
+pub use shape::Shape;
+#[cfg(feature = "std")] pub use shortest::{ShortestPaths, ShortestPathsIter};
+pub use simulate::{Outcome, Simulator};
+pub use view::{GridRead, GridRef, GridWrite};
+pub use visibility::group_by_direction;
+pub use windows::{Window, Windows};
+/// Re-exports used by [`layered_grid!`], so that it works in crates without `std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
 #[cfg(feature = "arbitrary")] use proptest::arbitrary::Arbitrary;
 #[cfg(feature = "arbitrary")]use proptest_derive::Arbitrary;
 
@@ -20,6 +66,14 @@ impl Position {
     pub fn is_zero(&self) -> bool {
         self.0 == 0 && self.1 == 0
     }
+
+    pub fn x(&self) -> usize {
+        self.0
+    }
+
+    pub fn y(&self) -> usize {
+        self.1
+    }
 }
 
 impl Add<&Offset> for &Position {
@@ -34,8 +88,8 @@ impl Add<&Offset> for &Position {
 impl Sub<&Offset> for &Position {
     type Output = Option<Position>;
     fn sub(self, rhs: &Offset) -> Self::Output {
-        let x = self.0.checked_add_signed(rhs.0 * -1)?;
-        let y = self.1.checked_add_signed(rhs.1 * -1)?;
+        let x = self.0.checked_add_signed(-rhs.0)?;
+        let y = self.1.checked_add_signed(-rhs.1)?;
         Some(Position(x,y))
     }
 }
@@ -48,7 +102,7 @@ impl Sub<&Position> for &Position {
 }
 
 impl Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "({},{})", self.0, self.1)
     }
 }
@@ -93,9 +147,32 @@ impl Offset {
         if other.0 > self.0 || other.1 > self.1 {
             return (0, self.clone())
         }
-        let d = std::cmp::min(self.0 / other.0, self.1 / other.1);
+        let d = core::cmp::min(self.0 / other.0, self.1 / other.1);
         (d, Offset(self.0 % (other.0 * d), self.1 % (other.1 * d)))
     }
+
+    /// Shrinks this vector to the smallest integer-valued vector pointing in the same direction. The zero vector is unchanged.
+    pub fn primitive(&self) -> Offset {
+        let d = gcd(self.0, self.1);
+        if d == 0 {
+            return self.clone();
+        }
+        Offset(self.0 / d, self.1 / d)
+    }
+
+    /// True if this vector is non-zero and no shorter integer-valued vector points in the same direction.
+    pub fn is_primitive(&self) -> bool {
+        gcd(self.0, self.1) == 1
+    }
+}
+
+/// Greatest common divisor of the absolute values. `gcd(0, 0)` is zero.
+fn gcd(x: isize, y: isize) -> isize {
+    let (mut x, mut y) = (x.abs(), y.abs());
+    while x != 0 {
+        (x, y) = (y % x, x);
+    }
+    y
 }
 
 impl Add<&Offset> for &Offset {
@@ -145,11 +222,96 @@ impl Mul<isize> for Offset {
 }
 
 impl Display for Offset {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "({:+},{:+})", self.0, self.1)
     }
 }
 
+/// One of the four cardinal directions, using the same screen orientation as [`Position`] (i.e. `Up` decreases y).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum Direction {
+    Up, Right, Down, Left
+}
+
+impl Direction {
+    /// All directions, in clockwise order starting from `Up`.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn to_offset(self) -> Offset {
+        match self {
+            Direction::Up => Offset(0, -1),
+            Direction::Right => Offset(1, 0),
+            Direction::Down => Offset(0, 1),
+            Direction::Left => Offset(-1, 0),
+        }
+    }
+
+    /// Returns the direction corresponding to a unit offset, or `None` for any other offset.
+    pub fn from_offset(offset: &Offset) -> Option<Direction> {
+        match (offset.0, offset.1) {
+            (0, -1) => Some(Direction::Up),
+            (1, 0) => Some(Direction::Right),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// The ASCII arrow used for this direction in puzzle inputs.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Direction::Up => "Up",
+            Direction::Right => "Right",
+            Direction::Down => "Down",
+            Direction::Left => "Left",
+        };
+        write!(f, "{s}")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Grid<T> {
     data: Vec<T>,
@@ -188,14 +350,19 @@ impl <T> Grid<T> {
         self.height
     }
 
-    pub fn map<F, S>(&self, mut f: F) -> Grid<S>
+    pub fn map<F, S>(&self, f: F) -> Grid<S>
     where
         F: FnMut(&T) -> S,
     {
-        let r = self.data.iter().map(|x| f(x)).collect::<Vec<_>>();
+        let r = self.data.iter().map(f).collect::<Vec<_>>();
         Grid { data: r, width: self.width, height: self.height }
     }
 
+    /// The cells in row-major order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
     pub fn iter(&self) -> core::slice::Iter<'_, T> {
         self.data.iter()
     }
@@ -211,6 +378,19 @@ impl <T> Grid<T> {
             None
         }
     }
+
+    /// The orthogonally adjacent positions that lie within the grid, as [`GridRead::neighbors`].
+    pub fn neighbors(&self, pos: &Position) -> impl Iterator<Item = Position> + '_ {
+        GridRead::neighbors(self, pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self[pos])
+        } else {
+            None
+        }
+    }
 }
 
 impl <T> Index<&Position> for Grid<T> {
@@ -229,13 +409,13 @@ impl <T> IndexMut<&Position> for Grid<T> {
 }
 
 impl <T: Display> Display for Grid<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for y in 0..self.height {
             for x in 0..self.width {
                 write!(f, "{}", self.data[self.to_index(x,y)])?;
             }
             if y < self.height - 1 {
-                writeln!(f, "")?;
+                writeln!(f)?;
             }
         }
         Ok(())
@@ -292,6 +472,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::erasing_op)]
         fn check_offset_zero(offset: Offset) {
             assert_eq!(offset, &offset + &Offset::new(0,0));
             assert_eq!(offset, &offset - &Offset::new(0,0));
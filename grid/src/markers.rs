@@ -0,0 +1,186 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::Display;
+
+use crate::{Grid, GridBuilder, ParseError, Position};
+
+/// How many times a group of markers may appear in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    ExactlyOne,
+    AtLeastOne,
+    Any,
+}
+
+impl Cardinality {
+    pub fn accepts(self, count: usize) -> bool {
+        match self {
+            Cardinality::ExactlyOne => count == 1,
+            Cardinality::AtLeastOne => count >= 1,
+            Cardinality::Any => true,
+        }
+    }
+}
+
+impl Display for Cardinality {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Cardinality::ExactlyOne => "exactly one",
+            Cardinality::AtLeastOne => "at least one",
+            Cardinality::Any => "any number",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MarkerGroup<T> {
+    chars: String,
+    substitute: T,
+    cardinality: Cardinality,
+}
+
+/// Parses text grids in which some characters mark interesting positions, such as a start, an end or a guard, rather than cell values.
+/// Each marker is replaced by a substitute cell and its positions are recorded.
+///
+/// ```
+/// # use grid::{Cardinality, Markers, Position};
+/// let marked = Markers::new()
+///     .marker("S", b'.', Cardinality::ExactlyOne)
+///     .marker("^>v<", b'.', Cardinality::ExactlyOne)
+///     .parse("S.#\n.>.\n", |c| Some(c as u8))
+///     .unwrap();
+/// assert_eq!(Some(&Position::new(0,0)), marked.first('S'));
+/// assert_eq!(Some(('>', &Position::new(1,1))), marked.find("^>v<"));
+/// assert_eq!(b'.', marked.grid[&Position::new(1,1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Markers<T> {
+    groups: Vec<MarkerGroup<T>>,
+}
+
+impl <T> Default for Markers<T> {
+    fn default() -> Self {
+        Markers { groups: Vec::new() }
+    }
+}
+
+/// A grid parsed by [`Markers::parse`], along with where each marker was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marked<T> {
+    pub grid: Grid<T>,
+    /// The positions of each marker that appeared, in row-major order.
+    pub markers: BTreeMap<char, Vec<Position>>,
+}
+
+impl <T: Clone> Markers<T> {
+    pub fn new() -> Self {
+        Markers::default()
+    }
+
+    /// Declares each character in `chars` as a marker to be replaced by `substitute`. The characters are counted together, so
+    /// `"^>v<"` with [`Cardinality::ExactlyOne`] accepts a single guard facing any direction.
+    pub fn marker(mut self, chars: &str, substitute: T, cardinality: Cardinality) -> Self {
+        self.groups.push(MarkerGroup { chars: chars.into(), substitute, cardinality });
+        self
+    }
+
+    /// Parses one row per line, converting characters that are not markers with `f`. Fails if `f` rejects a character,
+    /// the rows differ in length, or a group of markers appears the wrong number of times.
+    pub fn parse<F>(&self, input: &str, mut f: F) -> Result<Marked<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut builder = GridBuilder::new();
+        let mut markers: BTreeMap<char, Vec<Position>> = BTreeMap::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (x, c) in line.chars().enumerate() {
+                let cell = match self.groups.iter().find(|g| g.chars.contains(c)) {
+                    Some(group) => {
+                        markers.entry(c).or_default().push(Position(x, y));
+                        group.substitute.clone()
+                    },
+                    None => f(c).ok_or(ParseError::UnexpectedChar { pos: Position(x, y), c })?,
+                };
+                row.push(cell);
+            }
+            builder.push_row(row)?;
+        }
+        for group in &self.groups {
+            let found = group.chars.chars().filter_map(|c| markers.get(&c)).map(Vec::len).sum();
+            if !group.cardinality.accepts(found) {
+                return Err(ParseError::MarkerCount { markers: group.chars.clone(), expected: group.cardinality, found });
+            }
+        }
+        Ok(Marked { grid: builder.build(), markers })
+    }
+}
+
+impl <T> Marked<T> {
+    /// Every position of `marker`, in row-major order.
+    pub fn positions(&self, marker: char) -> &[Position] {
+        self.markers.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first position of `marker`, which is the only one for markers declared with [`Cardinality::ExactlyOne`].
+    pub fn first(&self, marker: char) -> Option<&Position> {
+        self.positions(marker).first()
+    }
+
+    /// The first marker in `chars` to appear, in the order of `chars`, with its first position.
+    pub fn find(&self, chars: &str) -> Option<(char, &Position)> {
+        chars.chars().find_map(|c| Some((c, self.first(c)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    fn tile(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Wall),
+            _ => None,
+        }
+    }
+
+    fn maze() -> Markers<Tile> {
+        Markers::new()
+            .marker("S", Tile::Open, Cardinality::ExactlyOne)
+            .marker("E", Tile::Open, Cardinality::AtLeastOne)
+            .marker("o", Tile::Wall, Cardinality::Any)
+    }
+
+    #[test]
+    fn extracts_markers() {
+        let marked = maze().parse("#S.#\n#.oE\n#E.#\n", tile).unwrap();
+        assert_eq!(Some(&Position::new(1,0)), marked.first('S'));
+        assert_eq!(&[Position::new(3,1), Position::new(1,2)], marked.positions('E'));
+        assert_eq!(&[Position::new(2,1)], marked.positions('o'));
+        assert_eq!(Tile::Open, marked.grid[&Position::new(1,0)]);
+        assert_eq!(Tile::Wall, marked.grid[&Position::new(2,1)]);
+        assert_eq!(None, marked.find("x"));
+        assert_eq!((4, 3), (marked.grid.width(), marked.grid.height()));
+    }
+
+    #[test]
+    fn checks_cardinality() {
+        let err = maze().parse("S.\nSE\n", tile).unwrap_err();
+        assert_eq!(ParseError::MarkerCount { markers: "S".into(), expected: Cardinality::ExactlyOne, found: 2 }, err);
+        assert_eq!("Expected exactly one of \"S\", found 2", err.to_string());
+        assert_eq!(
+            Err(ParseError::MarkerCount { markers: "E".into(), expected: Cardinality::AtLeastOne, found: 0 }),
+            maze().parse("S.\n..\n", tile));
+        assert_eq!(Err(ParseError::UnexpectedChar { pos: Position::new(1,1), c: 'x' }), maze().parse("S.\nEx\n", tile));
+        assert_eq!(Err(ParseError::RaggedRow { row: 1, expected: 2, found: 1 }), maze().parse("SE\n.\n", tile));
+        assert_eq!(vec![Position::new(0,0)], maze().parse("SE", tile).unwrap().positions('S'));
+    }
+}
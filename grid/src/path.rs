@@ -0,0 +1,322 @@
+use alloc::{collections::{BTreeMap, BTreeSet}, string::String, vec, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::{Direction, Grid, Position};
+
+/// A single entry in a [`Path`]: a position together with the heading on arrival there.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Step {
+    pub pos: Position,
+    pub dir: Direction,
+}
+
+/// How [`Path::render`] draws the cells that a path passes through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    /// `^>v<` for the heading in each cell, or `|`, `-` and `+` for cells entered with more than one heading.
+    Arrows,
+    /// Box-drawing lines joining each cell to its neighbors along the path.
+    BoxDrawing,
+}
+
+/// An ordered sequence of positions and headings, such as the route taken by a walker or one reconstructed from a search.
+///
+/// Consecutive steps normally differ by a single unit move. A turn in place is recorded as two steps at the same position with different headings.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Path { steps: Vec::new() }
+    }
+
+    /// Builds a path from a sequence of orthogonally adjacent positions, taking each heading from the move that arrived there.
+    /// The first position is given the `initial` heading.
+    ///
+    /// Returns `None` if any two consecutive positions are not adjacent.
+    pub fn from_positions<I>(initial: Direction, positions: I) -> Option<Path>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let mut path = Path::new();
+        for pos in positions {
+            let dir = match path.steps.last() {
+                None => initial,
+                Some(prev) => Direction::from_offset(&(&pos - &prev.pos))?,
+            };
+            path.push(pos, dir);
+        }
+        Some(path)
+    }
+
+    /// Reconstructs the path ending at `end` by following `predecessor` back until it returns `None`, e.g. using the parent links recorded by a breadth-first search.
+    /// The predecessor links must not contain a cycle.
+    pub fn reconstruct<F>(initial: Direction, end: Position, mut predecessor: F) -> Option<Path>
+    where
+        F: FnMut(&Position) -> Option<Position>,
+    {
+        let mut positions = vec![end];
+        while let Some(prev) = predecessor(positions.last().unwrap()) {
+            positions.push(prev);
+        }
+        positions.reverse();
+        Self::from_positions(initial, positions)
+    }
+
+    pub fn push(&mut self, pos: Position, dir: Direction) {
+        self.steps.push(Step { pos, dir });
+    }
+
+    /// The number of recorded steps, including turns in place.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Step> {
+        self.steps.iter()
+    }
+
+    pub fn first(&self) -> Option<&Step> {
+        self.steps.first()
+    }
+
+    pub fn last(&self) -> Option<&Step> {
+        self.steps.last()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.steps.iter().map(|step| &step.pos)
+    }
+
+    /// The total Manhattan distance travelled between consecutive steps. Turns in place add nothing.
+    pub fn distance(&self) -> usize {
+        self.steps.windows(2)
+            .map(|w| {
+                let diff = &w[1].pos - &w[0].pos;
+                diff.x().unsigned_abs() + diff.y().unsigned_abs()
+            })
+            .sum()
+    }
+
+    /// The number of times the heading changes from one step to the next.
+    pub fn turn_count(&self) -> usize {
+        self.steps.windows(2).filter(|w| w[0].dir != w[1].dir).count()
+    }
+
+    /// Positions that the path leaves and later comes back to, in sorted order. Turning in place is not a revisit.
+    pub fn revisits(&self) -> Vec<Position> {
+        let mut seen = BTreeSet::new();
+        let mut revisited = BTreeSet::new();
+        for visit in self.visits() {
+            let pos = &self.steps[visit.start].pos;
+            if !seen.insert(pos) {
+                revisited.insert(pos.clone());
+            }
+        }
+        revisited.into_iter().collect()
+    }
+
+    /// Positions where the path crosses itself, in sorted order: it passes straight through the position vertically on one visit and horizontally on another.
+    /// Revisits that turn at the shared position, such as a loop closing at a corner, are not counted.
+    pub fn self_intersections(&self) -> Vec<Position> {
+        // For each position, whether it has been passed straight through (vertically, horizontally)
+        let mut passes: BTreeMap<&Position, (bool, bool)> = BTreeMap::new();
+        for visit in self.visits() {
+            if visit.start == 0 || visit.end + 1 >= self.steps.len() {
+                continue;
+            }
+            let arrival = self.steps[visit.start].dir;
+            let departure = self.steps[visit.end + 1].dir;
+            if arrival == departure {
+                let entry = passes.entry(&self.steps[visit.start].pos).or_default();
+                if arrival.is_vertical() { entry.0 = true } else { entry.1 = true }
+            }
+        }
+        passes.into_iter()
+            .filter(|(_, (vertical, horizontal))| *vertical && *horizontal)
+            .map(|(pos, _)| pos.clone())
+            .collect()
+    }
+
+    /// Renders `grid` as its `Display` implementation would, with the cells along this path drawn according to `style`.
+    /// Steps outside the grid are ignored.
+    pub fn render<T: Display>(&self, grid: &Grid<T>, style: PathStyle) -> String {
+        let symbols = match style {
+            PathStyle::Arrows => self.arrow_symbols(grid),
+            PathStyle::BoxDrawing => self.box_symbols(grid),
+        };
+        let mut r = String::new();
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let pos = Position::new(x,y);
+                match symbols[&pos] {
+                    Some(c) => r.push(c),
+                    None => write!(r, "{}", grid[&pos]).unwrap(),
+                }
+            }
+            if y < grid.height() - 1 {
+                r.push('\n');
+            }
+        }
+        r
+    }
+
+    fn arrow_symbols<T>(&self, grid: &Grid<T>) -> Grid<Option<char>> {
+        let mut headings: Grid<u8> = grid.map(|_| 0);
+        for step in self.steps.iter().filter(|step| grid.in_bounds(&step.pos)) {
+            headings[&step.pos] |= dir_bit(step.dir);
+        }
+        headings.map(|&mask| {
+            let vertical = mask & (dir_bit(Direction::Up) | dir_bit(Direction::Down)) != 0;
+            let horizontal = mask & (dir_bit(Direction::Left) | dir_bit(Direction::Right)) != 0;
+            match Direction::ALL.iter().find(|dir| dir_bit(**dir) == mask) {
+                Some(dir) => Some(dir.arrow()),
+                None if vertical && horizontal => Some('+'),
+                None if vertical => Some('|'),
+                None if horizontal => Some('-'),
+                None => None,
+            }
+        })
+    }
+
+    fn box_symbols<T>(&self, grid: &Grid<T>) -> Grid<Option<char>> {
+        // Indexed by the bitmask of directions in which a cell connects to its neighbors on the path
+        const SYMBOLS: [char; 16] = ['·', '╵', '╶', '└', '╷', '│', '┌', '├', '╴', '┘', '─', '┴', '┐', '┤', '┬', '┼'];
+        let mut connections: Grid<Option<u8>> = grid.map(|_| None);
+        for step in self.steps.iter().filter(|step| grid.in_bounds(&step.pos)) {
+            connections[&step.pos].get_or_insert(0);
+        }
+        for w in self.steps.windows(2) {
+            if let Some(dir) = Direction::from_offset(&(&w[1].pos - &w[0].pos)) {
+                for (pos, dir) in [(&w[0].pos, dir), (&w[1].pos, dir.reverse())] {
+                    if let Some(Some(mask)) = connections.get_mut(pos) {
+                        *mask |= dir_bit(dir);
+                    }
+                }
+            }
+        }
+        connections.map(|mask| mask.map(|mask| SYMBOLS[mask as usize]))
+    }
+
+    /// Splits the path into maximal runs of consecutive steps at the same position.
+    fn visits(&self) -> impl Iterator<Item = Visit> + '_ {
+        let mut start = 0;
+        core::iter::from_fn(move || {
+            if start >= self.steps.len() {
+                return None;
+            }
+            let mut end = start;
+            while end + 1 < self.steps.len() && self.steps[end + 1].pos == self.steps[start].pos {
+                end += 1;
+            }
+            let visit = Visit { start, end };
+            start = end + 1;
+            Some(visit)
+        })
+    }
+}
+
+/// Inclusive range of step indices spent at one position.
+struct Visit {
+    start: usize,
+    end: usize,
+}
+
+fn dir_bit(dir: Direction) -> u8 {
+    1 << dir as u8
+}
+
+impl FromIterator<Step> for Path {
+    fn from_iter<I: IntoIterator<Item = Step>>(iter: I) -> Self {
+        Path { steps: iter.into_iter().collect() }
+    }
+}
+
+impl<'a> IntoIterator for &'a Path {
+    type Item = &'a Step;
+    type IntoIter = core::slice::Iter<'a, Step>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+
+    fn positions(coords: &[(usize, usize)]) -> Vec<Position> {
+        coords.iter().map(|&(x,y)| Position::new(x,y)).collect()
+    }
+
+    /// Heads down through the middle of a 3x3 board, then loops back and crosses the center horizontally.
+    fn crossing_path() -> Path {
+        let coords = [(1,0), (1,1), (1,2), (2,2), (2,1), (1,1), (0,1)];
+        Path::from_positions(Direction::Down, positions(&coords)).unwrap()
+    }
+
+    #[test]
+    fn metrics() {
+        let path = crossing_path();
+        assert_eq!(7, path.len());
+        assert_eq!(6, path.distance());
+        assert_eq!(3, path.turn_count());
+        assert_eq!(positions(&[(1,1)]), path.revisits());
+        assert_eq!(positions(&[(1,1)]), path.self_intersections());
+    }
+
+    #[test]
+    fn closed_loop_is_not_an_intersection() {
+        let coords = [(0,0), (1,0), (1,1), (0,1), (0,0)];
+        let path = Path::from_positions(Direction::Right, positions(&coords)).unwrap();
+        assert_eq!(positions(&[(0,0)]), path.revisits());
+        assert!(path.self_intersections().is_empty());
+    }
+
+    #[test]
+    fn turn_in_place() {
+        let mut path = Path::new();
+        path.push(Position::new(0,1), Direction::Up);
+        path.push(Position::new(0,0), Direction::Up);
+        path.push(Position::new(0,0), Direction::Right);
+        path.push(Position::new(1,0), Direction::Right);
+        assert_eq!(2, path.distance());
+        assert_eq!(1, path.turn_count());
+        assert!(path.revisits().is_empty());
+    }
+
+    #[test]
+    fn from_positions_rejects_gaps() {
+        assert!(Path::from_positions(Direction::Up, positions(&[(0,0), (2,0)])).is_none());
+        assert!(Path::from_positions(Direction::Up, positions(&[(0,0), (1,1)])).is_none());
+    }
+
+    #[test]
+    fn reconstruct() {
+        let predecessors: HashMap<Position, Position> = [((0,1), (0,0)), ((1,1), (0,1)), ((1,2), (1,1))].into_iter()
+            .map(|((x1,y1), (x2,y2))| (Position::new(x1,y1), Position::new(x2,y2)))
+            .collect();
+        let path = Path::reconstruct(Direction::Down, Position::new(1,2), |pos| predecessors.get(pos).cloned()).unwrap();
+        assert_eq!(positions(&[(0,0), (0,1), (1,1), (1,2)]), path.positions().cloned().collect::<Vec<_>>());
+        let dirs: Vec<Direction> = path.iter().map(|step| step.dir).collect();
+        assert_eq!(vec![Direction::Down, Direction::Down, Direction::Right, Direction::Down], dirs);
+    }
+
+    #[test]
+    fn render() {
+        let grid = Grid::new(vec!['.'; 9], 3, 3);
+        let path = crossing_path();
+        assert_eq!(".v.\n<+^\n.v>", path.render(&grid, PathStyle::Arrows));
+        assert_eq!(".╷.\n╶┼┐\n.└┘", path.render(&grid, PathStyle::BoxDrawing));
+    }
+}
@@ -0,0 +1,159 @@
+use core::fmt::Display;
+
+use crate::{Grid, Position};
+
+/// An axis-aligned rectangle of positions, described by its top-left corner and its dimensions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rect {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Rect {
+    pub fn new(top_left: Position, width: usize, height: usize) -> Self {
+        Rect { x: top_left.0, y: top_left.1, width, height }
+    }
+
+    /// The smallest rectangle containing both corners.
+    pub fn from_corners(a: &Position, b: &Position) -> Self {
+        let (x, y) = (a.0.min(b.0), a.1.min(b.1));
+        Rect { x, y, width: a.0.max(b.0) - x + 1, height: a.1.max(b.1) - y + 1 }
+    }
+
+    /// The smallest rectangle containing every position, or `None` if there are none.
+    pub fn bounding<'a, I: IntoIterator<Item = &'a Position>>(positions: I) -> Option<Self> {
+        positions.into_iter()
+            .map(|pos| Rect::from_corners(pos, pos))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    pub fn top_left(&self) -> Position {
+        Position(self.x, self.y)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The x coordinate one past the right-most column.
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// The y coordinate one past the bottom row.
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        (self.x..self.right()).contains(&pos.0) && (self.y..self.bottom()).contains(&pos.1)
+    }
+
+    /// True if every position in `other` is also in this rectangle. An empty rectangle is contained by any other.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.is_empty() || (self.x <= other.x && self.y <= other.y && other.right() <= self.right() && other.bottom() <= self.bottom())
+    }
+
+    /// The positions in both rectangles, or `None` if they do not overlap.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let (right, bottom) = (self.right().min(other.right()), self.bottom().min(other.bottom()));
+        (x < right && y < bottom).then(|| Rect { x, y, width: right - x, height: bottom - y })
+    }
+
+    /// The smallest rectangle containing both rectangles. Empty rectangles are ignored.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return self.clone();
+        }
+        if self.is_empty() {
+            return other.clone();
+        }
+        let (x, y) = (self.x.min(other.x), self.y.min(other.y));
+        Rect { x, y, width: self.right().max(other.right()) - x, height: self.bottom().max(other.bottom()) - y }
+    }
+
+    /// Grows the rectangle by `n` on every side, stopping at zero on the top and left.
+    pub fn expand(&self, n: usize) -> Rect {
+        let (x, y) = (self.x.saturating_sub(n), self.y.saturating_sub(n));
+        Rect { x, y, width: self.right() + n - x, height: self.bottom() + n - y }
+    }
+
+    /// Every position in the rectangle, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (xs, ys) = (self.x..self.right(), self.y..self.bottom());
+        ys.flat_map(move |y| xs.clone().map(move |x| Position(x, y)))
+    }
+}
+
+impl Display for Rect {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}x{}@{}", self.width, self.height, self.top_left())
+    }
+}
+
+impl <T> Grid<T> {
+    /// The rectangle covering the whole grid.
+    pub fn rect(&self) -> Rect {
+        Rect::new(Position(0, 0), self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn rect() -> impl Strategy<Value = Rect> {
+        (0usize..20, 0usize..20, 0usize..10, 0usize..10).prop_map(|(x, y, w, h)| Rect::new(Position::new(x,y), w, h))
+    }
+
+    proptest! {
+        #[test]
+        fn check_intersect_union(a in rect(), b in rect()) {
+            let union = a.union(&b);
+            assert!(union.contains_rect(&a));
+            assert!(union.contains_rect(&b));
+            match a.intersect(&b) {
+                Some(both) => {
+                    assert!(a.contains_rect(&both) && b.contains_rect(&both));
+                    assert_eq!(both.area(), a.positions().filter(|pos| b.contains(pos)).count());
+                },
+                None => assert!(a.positions().all(|pos| !b.contains(&pos))),
+            }
+        }
+
+        #[test]
+        fn check_positions(a in rect()) {
+            assert_eq!(a.area(), a.positions().count());
+            assert!(a.positions().all(|pos| a.contains(&pos)));
+            assert_eq!(Rect::bounding(a.positions().collect::<Vec<_>>().iter()).unwrap_or(Rect::new(Position::new(0,0), 0, 0)).area(), a.area());
+        }
+    }
+
+    #[test]
+    fn expand_and_grid() {
+        let grid = Grid::new(vec![0; 12], 4, 3);
+        let rect = Rect::from_corners(&Position::new(2,1), &Position::new(1,1));
+        assert_eq!(Rect::new(Position::new(1,1), 2, 1), rect);
+        let expanded = rect.expand(2);
+        assert_eq!(Rect::new(Position::new(0,0), 5, 4), expanded);
+        assert!(!grid.rect().contains_rect(&expanded));
+        assert_eq!(Some(grid.rect()), expanded.intersect(&grid.rect()));
+        assert_eq!(Rect::new(Position::new(0,0), 0, 0).union(&rect), rect);
+    }
+}
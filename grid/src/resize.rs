@@ -0,0 +1,167 @@
+use alloc::vec::Vec;
+use crate::{Grid, Rect};
+
+/// Number of cells to add on each side of a grid with [`Grid::pad`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Padding {
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub left: usize,
+}
+
+impl Padding {
+    pub fn uniform(amount: usize) -> Self {
+        Padding { top: amount, right: amount, bottom: amount, left: amount }
+    }
+}
+
+impl <T: Clone> Grid<T> {
+    /// Returns a copy of this grid surrounded by `fill`. Positions in the new grid are shifted by `(padding.left, padding.top)`.
+    ///
+    /// A border of sentinel values lets neighbor lookups skip bounds checks for every cell of the original grid.
+    pub fn pad(&self, padding: Padding, fill: T) -> Grid<T> {
+        let width = self.width + padding.left + padding.right;
+        let height = self.height + padding.top + padding.bottom;
+        let mut data = Vec::with_capacity(width * height);
+        data.resize(width * padding.top, fill.clone());
+        for row in self.data.chunks(self.width.max(1)) {
+            data.extend(core::iter::repeat_n(fill.clone(), padding.left));
+            data.extend_from_slice(row);
+            data.extend(core::iter::repeat_n(fill.clone(), padding.right));
+        }
+        data.resize(width * height, fill);
+        Grid { data, width, height }
+    }
+
+    /// Returns a copy of the cells inside `rect`.
+    ///
+    /// Panics if `rect` extends past the edge of the grid.
+    pub fn crop(&self, rect: &Rect) -> Grid<T> {
+        if rect.right() > self.width || rect.bottom() > self.height {
+            panic!("Crop region {rect} exceeds grid of size {}x{}", self.width, self.height);
+        }
+        if rect.width() == 0 || rect.height() == 0 {
+            return Grid { data: Vec::new(), width: rect.width(), height: rect.height() };
+        }
+        let mut data = Vec::with_capacity(rect.width() * rect.height());
+        let left = rect.top_left().0;
+        for y in rect.top_left().1..rect.bottom() {
+            let start = self.to_index(left, y);
+            data.extend_from_slice(&self.data[start..start + rect.width()]);
+        }
+        Grid { data, width: rect.width(), height: rect.height() }
+    }
+
+    /// Places the grids side by side, left to right.
+    ///
+    /// Panics if the grids do not all have the same height.
+    pub fn concat_horizontal<'a, I>(grids: I) -> Grid<T>
+    where
+        I: IntoIterator<Item = &'a Grid<T>>,
+        T: 'a,
+    {
+        let grids: Vec<&Grid<T>> = grids.into_iter().collect();
+        let height = grids.first().map_or(0, |g| g.height);
+        if let Some(g) = grids.iter().find(|g| g.height != height) {
+            panic!("Cannot concatenate grids of height {height} and {} horizontally", g.height);
+        }
+        let width = grids.iter().map(|g| g.width).sum();
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for g in grids.iter() {
+                data.extend_from_slice(&g.data[y * g.width..(y + 1) * g.width]);
+            }
+        }
+        Grid { data, width, height }
+    }
+
+    /// Stacks the grids on top of each other, top to bottom.
+    ///
+    /// Panics if the grids do not all have the same width.
+    pub fn concat_vertical<'a, I>(grids: I) -> Grid<T>
+    where
+        I: IntoIterator<Item = &'a Grid<T>>,
+        T: 'a,
+    {
+        let grids: Vec<&Grid<T>> = grids.into_iter().collect();
+        let width = grids.first().map_or(0, |g| g.width);
+        if let Some(g) = grids.iter().find(|g| g.width != width) {
+            panic!("Cannot concatenate grids of width {width} and {} vertically", g.width);
+        }
+        let height = grids.iter().map(|g| g.height).sum();
+        let mut data = Vec::with_capacity(width * height);
+        for g in grids.iter() {
+            data.extend_from_slice(&g.data);
+        }
+        Grid { data, width, height }
+    }
+
+    /// Repeats this grid `across` times horizontally and `down` times vertically.
+    pub fn tile(&self, across: usize, down: usize) -> Grid<T> {
+        let row = Self::concat_horizontal(core::iter::repeat_n(self, across));
+        let mut r = Self::concat_vertical(core::iter::repeat_n(&row, down));
+        // Concatenating zero grids loses the other dimension
+        r.width = self.width * across;
+        r.height = self.height * down;
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Position;
+    use super::*;
+
+    fn digits(width: usize, height: usize) -> Grid<u8> {
+        Grid::new((0..(width * height) as u8).collect(), width, height)
+    }
+
+    #[test]
+    fn pad() {
+        let grid = digits(2, 2);
+        let padded = grid.pad(Padding { top: 1, right: 0, bottom: 2, left: 1 }, 9);
+        assert_eq!(3, padded.width());
+        assert_eq!(5, padded.height());
+        assert_eq!("999\n901\n923\n999\n999", padded.to_string());
+        assert_eq!(grid, padded.crop(&Rect::new(Position::new(1,1), 2, 2)));
+    }
+
+    #[test]
+    fn crop() {
+        let grid = digits(3, 3);
+        assert_eq!("45\n78", grid.crop(&Rect::new(Position::new(1,1), 2, 2)).to_string());
+        assert_eq!("3", grid.crop(&Rect::new(Position::new(0,1), 1, 1)).to_string());
+    }
+
+    #[test]
+    #[should_panic]
+    fn crop_out_of_bounds() {
+        digits(3, 3).crop(&Rect::new(Position::new(2,2), 2, 1));
+    }
+
+    #[test]
+    fn crop_empty_at_edge() {
+        let grid = digits(3, 3);
+        assert_eq!(Grid::new(Vec::new(), 0, 2), grid.crop(&Rect::new(Position::new(3,1), 0, 2)));
+        assert_eq!(Grid::new(Vec::new(), 2, 0), grid.crop(&Rect::new(Position::new(1,3), 2, 0)));
+    }
+
+    #[test]
+    fn concat() {
+        let a = digits(2, 2);
+        let b = digits(1, 2);
+        assert_eq!("010\n231", Grid::concat_horizontal([&a, &b]).to_string());
+        let c = digits(2, 1);
+        assert_eq!("01\n23\n01", Grid::concat_vertical([&a, &c]).to_string());
+    }
+
+    #[test]
+    fn tile() {
+        let grid = digits(2, 1);
+        let tiled = grid.tile(3, 2);
+        assert_eq!("010101\n010101", tiled.to_string());
+        let empty = grid.tile(0, 2);
+        assert_eq!((0, 2), (empty.width(), empty.height()));
+    }
+}
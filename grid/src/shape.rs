@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::{Grid, Offset, Position};
+
+/// A set of cells, such as a polyomino, kept normalized so that its top-most row and left-most column are at zero.
+/// Two shapes are equal if they have the same cells in the same orientation; compare [canonical](Self::canonical) forms to
+/// test equality up to rotation and reflection.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Shape {
+    /// Sorted and free of duplicates.
+    cells: Vec<Offset>,
+}
+
+impl Shape {
+    pub fn from_offsets<I: IntoIterator<Item = Offset>>(offsets: I) -> Self {
+        let mut cells: Vec<Offset> = offsets.into_iter().collect();
+        let min_x = cells.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        for cell in cells.iter_mut() {
+            *cell = Offset(cell.0 - min_x, cell.1 - min_y);
+        }
+        cells.sort();
+        cells.dedup();
+        Shape { cells }
+    }
+
+    pub fn from_positions<I: IntoIterator<Item = Position>>(positions: I) -> Self {
+        Shape::from_offsets(positions.into_iter().map(|pos| Offset(pos.0 as isize, pos.1 as isize)))
+    }
+
+    /// The cells of the shape relative to the top-left corner of its bounding box, in sorted order.
+    pub fn cells(&self) -> &[Offset] {
+        &self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The width of the shape's bounding box.
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(|c| c.0 as usize + 1).max().unwrap_or(0)
+    }
+
+    /// The height of the shape's bounding box.
+    pub fn height(&self) -> usize {
+        self.cells.iter().map(|c| c.1 as usize + 1).max().unwrap_or(0)
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate(&self) -> Shape {
+        Shape::from_offsets(self.cells.iter().map(|c| Offset(-c.1, c.0)))
+    }
+
+    /// Mirrors left to right.
+    pub fn reflect(&self) -> Shape {
+        Shape::from_offsets(self.cells.iter().map(|c| Offset(-c.0, c.1)))
+    }
+
+    /// The four rotations of the shape followed by the four rotations of its reflection, starting with the shape itself.
+    /// Symmetric shapes appear more than once.
+    pub fn symmetries(&self) -> [Shape; 8] {
+        let mut r: [Shape; 8] = Default::default();
+        r[0] = self.clone();
+        r[4] = self.reflect();
+        for i in [1, 2, 3, 5, 6, 7] {
+            r[i] = r[i - 1].rotate();
+        }
+        r
+    }
+
+    /// The distinct orientations of the shape, in sorted order.
+    pub fn orientations(&self) -> Vec<Shape> {
+        let mut r = self.symmetries().to_vec();
+        r.sort();
+        r.dedup();
+        r
+    }
+
+    /// A representative orientation that is the same for every rotation and reflection of the shape.
+    pub fn canonical(&self) -> Shape {
+        self.symmetries().into_iter().min().unwrap()
+    }
+
+    /// True if `other` is a rotation or reflection of this shape.
+    pub fn is_congruent(&self, other: &Shape) -> bool {
+        self.len() == other.len() && self.canonical() == other.canonical()
+    }
+
+    /// True if the shape, with its top-left corner at `at`, lies within the grid and covers only cells accepted by `allowed`.
+    pub fn fits<T, F>(&self, grid: &Grid<T>, at: &Position, mut allowed: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.cells.iter().all(|cell| {
+            (at + cell).and_then(|pos| grid.get(&pos)).is_some_and(&mut allowed)
+        })
+    }
+
+    /// Every position at which the shape [fits](Self::fits) in this orientation, in row-major order.
+    pub fn placements<'a, T, F>(&'a self, grid: &'a Grid<T>, mut allowed: F) -> impl Iterator<Item = Position> + 'a
+    where
+        F: FnMut(&T) -> bool + 'a,
+    {
+        let xs = 0..(grid.width() + 1).saturating_sub(self.width());
+        let ys = 0..(grid.height() + 1).saturating_sub(self.height());
+        ys.flat_map(move |y| xs.clone().map(move |x| Position(x, y)))
+            .filter(move |at| self.fits(grid, at, &mut allowed))
+    }
+}
+
+/// Draws the shape's bounding box, with `#` for its cells and `.` elsewhere.
+impl Display for Shape {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let c = if self.cells.binary_search(&Offset(x as isize, y as isize)).is_ok() { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            if y < self.height() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(rows: &[&str]) -> Shape {
+        let positions = rows.iter().enumerate()
+            .flat_map(|(y, row)| row.char_indices().filter(|(_, c)| *c == '#').map(move |(x, _)| Position::new(x,y)));
+        Shape::from_positions(positions)
+    }
+
+    proptest! {
+        #[test]
+        fn check_symmetries(offsets in prop::collection::vec((-5isize..5, -5isize..5), 1..8), turns in 0usize..4, flip: bool) {
+            let shape = Shape::from_offsets(offsets.into_iter().map(|(x, y)| Offset::new(x,y)));
+            let mut moved = if flip { shape.reflect() } else { shape.clone() };
+            for _ in 0..turns {
+                moved = moved.rotate();
+            }
+            assert_eq!(shape.canonical(), moved.canonical());
+            assert!(shape.symmetries().contains(&moved));
+            assert_eq!(shape, shape.rotate().rotate().rotate().rotate());
+            assert_eq!((shape.width(), shape.height()), (shape.rotate().height(), shape.rotate().width()));
+        }
+    }
+
+    #[test]
+    fn tetrominoes() {
+        assert_eq!(8, parse(&["#.", "#.", "##"]).orientations().len());
+        assert_eq!(4, parse(&["###", ".#."]).orientations().len());
+        assert_eq!(4, parse(&[".##", "##."]).orientations().len());
+        assert_eq!(2, parse(&["####"]).orientations().len());
+        assert_eq!(1, parse(&["##", "##"]).orientations().len());
+        assert!(parse(&["#.", "#.", "##"]).is_congruent(&parse(&["###", "#.."])));
+        assert!(!parse(&["###", ".#."]).is_congruent(&parse(&["####"])));
+        assert_eq!("###\n#..", parse(&["..#", "###"]).rotate().rotate().to_string());
+    }
+
+    #[test]
+    fn placement() {
+        let grid: Grid<u8> = ["..#", "...", "#.."].iter().map(|row| row.bytes()).collect();
+        let shape = parse(&["##", "#."]);
+        assert!(shape.fits(&grid, &Position::new(0,0), |c| *c == b'.'));
+        assert!(!shape.fits(&grid, &Position::new(2,2), |c| *c == b'.'));
+        assert_eq!(vec![Position::new(0,0), Position::new(1,1)], shape.placements(&grid, |c| *c == b'.').collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,308 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash};
+
+use crate::{Direction, Grid, Path, Position};
+
+/// The DAG of optimal moves found by a shortest-path search: every node reached, its distance from the nearest start,
+/// and each predecessor through which it can be reached at that distance.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N> {
+    ids: HashMap<N, usize>,
+    nodes: Vec<N>,
+    dist: Vec<u64>,
+    preds: Vec<Vec<usize>>,
+    /// Node ids in the order they were settled, which is non-decreasing by distance.
+    order: Vec<usize>,
+}
+
+impl <N: Clone + Eq + Hash> ShortestPaths<N> {
+    fn with_starts<I: IntoIterator<Item = N>>(starts: I) -> Self {
+        let mut r = ShortestPaths { ids: HashMap::new(), nodes: Vec::new(), dist: Vec::new(), preds: Vec::new(), order: Vec::new() };
+        for start in starts {
+            r.node_id(start, 0);
+        }
+        r
+    }
+
+    /// Returns the id for `node`, recording it with tentative distance `dist` if it is new.
+    fn node_id(&mut self, node: N, dist: u64) -> usize {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.dist.push(dist);
+        self.preds.push(Vec::new());
+        id
+    }
+
+    /// Searches outward from `starts` where every move costs one.
+    pub fn bfs<I, F, E>(starts: I, mut successors: F) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        F: FnMut(&N) -> E,
+        E: IntoIterator<Item = N>,
+    {
+        let mut r = Self::with_starts(starts);
+        let mut queue: VecDeque<usize> = (0..r.nodes.len()).collect();
+        while let Some(id) = queue.pop_front() {
+            r.order.push(id);
+            let next_dist = r.dist[id] + 1;
+            for next in successors(&r.nodes[id]) {
+                let is_new = !r.ids.contains_key(&next);
+                let next_id = r.node_id(next, next_dist);
+                if is_new {
+                    queue.push_back(next_id);
+                }
+                if r.dist[next_id] == next_dist {
+                    r.preds[next_id].push(id);
+                }
+            }
+        }
+        r
+    }
+
+    /// Searches outward from `starts` using Dijkstra's algorithm. Successors are paired with the cost of moving to them, which must be positive.
+    pub fn dijkstra<I, F, E>(starts: I, mut successors: F) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        F: FnMut(&N) -> E,
+        E: IntoIterator<Item = (N, u64)>,
+    {
+        let mut r = Self::with_starts(starts);
+        let mut settled: Vec<bool> = vec![false; r.nodes.len()];
+        let mut queue: BinaryHeap<Reverse<(u64, usize)>> = (0..r.nodes.len()).map(|id| Reverse((0, id))).collect();
+        while let Some(Reverse((dist, id))) = queue.pop() {
+            if settled[id] || dist > r.dist[id] {
+                continue;
+            }
+            settled[id] = true;
+            r.order.push(id);
+            for (next, cost) in successors(&r.nodes[id]) {
+                assert!(cost > 0, "Shortest path edge costs must be positive");
+                let next_dist = dist + cost;
+                let next_id = r.node_id(next, next_dist);
+                if next_id == settled.len() {
+                    settled.push(false);
+                    queue.push(Reverse((next_dist, next_id)));
+                }
+                if settled[next_id] || next_dist > r.dist[next_id] {
+                    continue;
+                }
+                if next_dist < r.dist[next_id] {
+                    r.dist[next_id] = next_dist;
+                    r.preds[next_id].clear();
+                    queue.push(Reverse((next_dist, next_id)));
+                }
+                r.preds[next_id].push(id);
+            }
+        }
+        r
+    }
+
+    /// The length of the shortest path to `node`, or `None` if it was not reached.
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.ids.get(node).map(|&id| self.dist[id])
+    }
+
+    /// The nodes immediately before `node` on its shortest paths. Empty for start nodes and unreached nodes.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let preds = self.ids.get(node).map_or(&[][..], |&id| &self.preds[id]);
+        preds.iter().map(|&id| &self.nodes[id])
+    }
+
+    /// All nodes reached by the search, nearest first.
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.order.iter().map(|&id| &self.nodes[id])
+    }
+
+    /// The number of distinct shortest paths from any start to `target`: zero if it was not reached, or `None` if the count overflows a `u128`.
+    pub fn count_paths(&self, target: &N) -> Option<u128> {
+        let Some(&target) = self.ids.get(target) else {
+            return Some(0);
+        };
+        // Every predecessor is settled before its successors, so one pass in settled order sees each count complete before it is used
+        let mut counts: Vec<Option<u128>> = vec![Some(0); self.nodes.len()];
+        for &id in self.order.iter() {
+            counts[id] = if self.preds[id].is_empty() {
+                Some(1)
+            } else {
+                self.preds[id].iter().try_fold(0u128, |sum, &pred| sum.checked_add(counts[pred]?))
+            };
+            if id == target {
+                break;
+            }
+        }
+        counts[target]
+    }
+
+    /// Every node that lies on at least one shortest path to any of `targets`, including the targets themselves if they were reached.
+    pub fn on_shortest_paths<'a, I>(&self, targets: I) -> HashSet<N>
+    where
+        I: IntoIterator<Item = &'a N>,
+        N: 'a,
+    {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = targets.into_iter().filter_map(|target| self.ids.get(target).copied()).collect();
+        let mut r = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if !std::mem::replace(&mut seen[id], true) {
+                r.insert(self.nodes[id].clone());
+                stack.extend(self.preds[id].iter().copied());
+            }
+        }
+        r
+    }
+
+    /// Lazily enumerates every shortest path to `target`, each listed from its start to `target`.
+    /// Searches over grid positions can use [`paths_to`](ShortestPaths::paths_to) instead to get each one as a [`Path`].
+    pub fn node_paths_to(&self, target: &N) -> impl Iterator<Item = Vec<N>> + '_ {
+        let mut cursor = PathCursor::new(self, target);
+        std::iter::from_fn(move || Some(cursor.next(self)?.map(|id| self.nodes[id].clone()).collect()))
+    }
+}
+
+impl ShortestPaths<Position> {
+    /// Lazily enumerates every shortest path to `target`. The first step of each path is given the `initial` heading.
+    pub fn paths_to(&self, target: &Position, initial: Direction) -> ShortestPathsIter<'_> {
+        ShortestPathsIter { paths: self, cursor: PathCursor::new(self, target), initial }
+    }
+}
+
+/// Depth-first walk over the predecessor links back from one target, yielding each complete path in turn.
+#[derive(Debug, Clone)]
+struct PathCursor {
+    /// The current path, from the target backwards, with the index of the next predecessor to try at each node.
+    stack: Vec<(usize, usize)>,
+}
+
+impl PathCursor {
+    fn new<N: Eq + Hash>(paths: &ShortestPaths<N>, target: &N) -> Self {
+        PathCursor { stack: paths.ids.get(target).map(|&id| (id, 0)).into_iter().collect() }
+    }
+
+    /// The node ids of the next path, from its start to the target.
+    fn next<'a, N>(&'a mut self, paths: &ShortestPaths<N>) -> Option<impl Iterator<Item = usize> + 'a> {
+        while let Some((id, cursor)) = self.stack.last_mut() {
+            let preds = &paths.preds[*id];
+            if preds.is_empty() && *cursor == 0 {
+                // Reached a start node, so the stack holds a complete path
+                *cursor = 1;
+                return Some(self.stack.iter().rev().map(|(id, _)| *id));
+            }
+            match preds.get(*cursor) {
+                Some(&pred) => {
+                    *cursor += 1;
+                    self.stack.push((pred, 0));
+                },
+                None => {
+                    self.stack.pop();
+                },
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the paths to one position, returned by [`ShortestPaths::paths_to`].
+pub struct ShortestPathsIter<'a> {
+    paths: &'a ShortestPaths<Position>,
+    cursor: PathCursor,
+    initial: Direction,
+}
+
+impl Iterator for ShortestPathsIter<'_> {
+    type Item = Path;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ids = self.cursor.next(self.paths)?;
+        let positions = ids.map(|id| self.paths.nodes[id].clone());
+        Some(Path::from_positions(self.initial, positions).expect("grid searches move between adjacent positions"))
+    }
+}
+
+impl <T> Grid<T> {
+    /// Breadth-first search over orthogonal moves within the grid, where `can_move(from, to)` decides whether a move between two cells is allowed.
+    pub fn shortest_paths<I, F>(&self, starts: I, mut can_move: F) -> ShortestPaths<Position>
+    where
+        I: IntoIterator<Item = Position>,
+        F: FnMut(&T, &T) -> bool,
+    {
+        ShortestPaths::bfs(starts, |pos| {
+            self.neighbors(pos)
+                .filter(|next| can_move(&self[pos], &self[next]))
+                .collect::<Vec<_>>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_grid(width: usize, height: usize) -> Grid<u8> {
+        Grid::new(vec![b'.'; width * height], width, height)
+    }
+
+    fn binomial(n: u128, k: u128) -> u128 {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn lattice_paths() {
+        let grid = open_grid(5, 4);
+        let paths = grid.shortest_paths([Position::new(0,0)], |_, to| *to == b'.');
+        let corner = Position::new(4,3);
+        assert_eq!(Some(7), paths.distance(&corner));
+        assert_eq!(Some(binomial(7, 3)), paths.count_paths(&corner));
+        assert_eq!(20, paths.on_shortest_paths([&corner]).len());
+
+        let all: Vec<Path> = paths.paths_to(&corner, Direction::Right).collect();
+        assert_eq!(binomial(7, 3) as usize, all.len());
+        for path in all.iter() {
+            assert_eq!(8, path.len());
+            assert_eq!(7, path.distance());
+            assert_eq!(Some(&Position::new(0,0)), path.first().map(|step| &step.pos));
+            assert_eq!(Some(&corner), path.last().map(|step| &step.pos));
+        }
+    }
+
+    #[test]
+    fn walls() {
+        let grid = Grid::new(b"...#....".to_vec(), 4, 2);
+        let paths = grid.shortest_paths([Position::new(0,0)], |_, to| *to == b'.');
+        assert_eq!(Some(4), paths.distance(&Position::new(3,1)));
+        assert_eq!(Some(3), paths.count_paths(&Position::new(3,1)));
+        assert_eq!(None, paths.distance(&Position::new(3,0)));
+        assert_eq!(Some(0), paths.count_paths(&Position::new(3,0)));
+        assert_eq!(0, paths.paths_to(&Position::new(3,0), Direction::Up).count());
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_routes() {
+        // Two routes from 0 to 3: 0-1-3 costs 2, 0-2-3 costs 2, 0-3 directly costs 5
+        let edges: HashMap<u32, Vec<(u32, u64)>> = HashMap::from([
+            (0, vec![(1, 1), (2, 1), (3, 5)]),
+            (1, vec![(3, 1)]),
+            (2, vec![(3, 1)]),
+        ]);
+        let paths = ShortestPaths::dijkstra([0], |n| edges.get(n).cloned().unwrap_or_default());
+        assert_eq!(Some(2), paths.distance(&3));
+        assert_eq!(Some(2), paths.count_paths(&3));
+        let mut all: Vec<Vec<u32>> = paths.node_paths_to(&3).collect();
+        all.sort();
+        assert_eq!(vec![vec![0, 1, 3], vec![0, 2, 3]], all);
+    }
+
+    #[test]
+    fn count_overflow() {
+        // A chain of diamonds doubles the path count at each step
+        let paths = ShortestPaths::bfs([0u32], |n| match n % 3 {
+            0 if *n < 3 * 130 => vec![n + 1, n + 2],
+            1 | 2 => vec![n - (n % 3) + 3],
+            _ => vec![],
+        });
+        assert_eq!(Some(1 << 100), paths.count_paths(&300));
+        assert_eq!(None, paths.count_paths(&390));
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(feature = "std")] use std::{collections::HashMap, hash::Hash};
+
+/// The result of running a [`Simulator`] until it either halts or repeats a state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<S> {
+    /// The step function returned `None` after `steps` steps. `state` is the last state reached.
+    Halted { steps: usize, state: S },
+    /// The states from step `start` onward repeat with period `length`. `state` is the state at step `start`, the first one to recur.
+    Cycle { start: usize, length: usize, state: S },
+}
+
+/// Repeatedly applies a step function to a state, detecting when the sequence of states enters a cycle.
+///
+/// The step function returns `None` once the simulation halts. It must be deterministic: the same state always produces the same successor.
+pub struct Simulator<S, F> {
+    initial: S,
+    step: F,
+}
+
+impl <S, F> Simulator<S, F>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    pub fn new(initial: S, step: F) -> Self {
+        Simulator { initial, step }
+    }
+
+    /// Detects a cycle with Floyd's tortoise-and-hare algorithm, which stores only two states at a time.
+    pub fn floyd(&mut self) -> Outcome<S> {
+        let mut tortoise = self.initial.clone();
+        let mut hare = self.initial.clone();
+        let mut steps = 0;
+        loop {
+            for _ in 0..2 {
+                match (self.step)(&hare) {
+                    Some(next) => hare = next,
+                    None => return Outcome::Halted { steps, state: hare },
+                }
+                steps += 1;
+            }
+            tortoise = self.cycle_step(&tortoise);
+            if tortoise == hare {
+                break;
+            }
+        }
+
+        // The distance between the two is now a multiple of the cycle length, so stepping both at the same pace meets at the start of the cycle.
+        let mut tortoise = self.initial.clone();
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = self.cycle_step(&tortoise);
+            hare = self.cycle_step(&hare);
+            start += 1;
+        }
+
+        let mut length = 1;
+        let mut hare = self.cycle_step(&tortoise);
+        while tortoise != hare {
+            hare = self.cycle_step(&hare);
+            length += 1;
+        }
+        Outcome::Cycle { start, length, state: tortoise }
+    }
+
+    /// Detects a cycle with Brent's algorithm, which stores only two states at a time and usually calls the step function fewer times than Floyd's.
+    pub fn brent(&mut self) -> Outcome<S> {
+        let mut power = 1;
+        let mut length = 1;
+        let mut steps = 0;
+        let mut tortoise = self.initial.clone();
+        let mut hare = match (self.step)(&tortoise) {
+            Some(next) => next,
+            None => return Outcome::Halted { steps, state: tortoise },
+        };
+        steps += 1;
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = match (self.step)(&hare) {
+                Some(next) => next,
+                None => return Outcome::Halted { steps, state: hare },
+            };
+            steps += 1;
+            length += 1;
+        }
+
+        let mut tortoise = self.initial.clone();
+        let mut hare = self.initial.clone();
+        for _ in 0..length {
+            hare = self.cycle_step(&hare);
+        }
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = self.cycle_step(&tortoise);
+            hare = self.cycle_step(&hare);
+            start += 1;
+        }
+        Outcome::Cycle { start, length, state: tortoise }
+    }
+
+    /// Detects a cycle by recording every state in a hash table. This visits each state only once, at the cost of memory.
+    #[cfg(feature = "std")]
+    pub fn hashed(&mut self) -> Outcome<S>
+    where
+        S: Hash,
+    {
+        let mut seen: HashMap<S, usize> = HashMap::new();
+        let mut state = self.initial.clone();
+        let mut steps = 0;
+        loop {
+            if let Some(&start) = seen.get(&state) {
+                return Outcome::Cycle { start, length: steps - start, state };
+            }
+            seen.insert(state.clone(), steps);
+            match (self.step)(&state) {
+                Some(next) => state = next,
+                None => return Outcome::Halted { steps, state },
+            }
+            steps += 1;
+        }
+    }
+
+    /// Returns the state after `n` steps, using a previously detected `outcome` to skip whole periods of the cycle.
+    /// A halted simulation stays in its final state.
+    pub fn state_at(&mut self, n: usize, outcome: &Outcome<S>) -> S {
+        let (mut state, remaining) = match outcome {
+            Outcome::Halted { steps, state } if n >= *steps => return state.clone(),
+            Outcome::Cycle { start, length, state } if n >= *start => (state.clone(), (n - start) % length),
+            _ => (self.initial.clone(), n),
+        };
+        for _ in 0..remaining {
+            state = self.cycle_step(&state);
+        }
+        state
+    }
+
+    /// Steps a state that is known to have a successor, because it is part of the sequence before a detected cycle or halt.
+    fn cycle_step(&mut self, state: &S) -> S {
+        (self.step)(state).expect("step function is deterministic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A random function on `0..n`, where `None` halts the simulation.
+    fn transitions() -> impl Strategy<Value = Vec<Option<usize>>> {
+        (1..50usize).prop_flat_map(|n| proptest::collection::vec(proptest::option::of(0..n), n))
+    }
+
+    fn naive_state_at(transitions: &[Option<usize>], n: usize) -> usize {
+        let mut state = 0;
+        for _ in 0..n {
+            match transitions[state] {
+                Some(next) => state = next,
+                None => break,
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn simple_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+        let mut sim = Simulator::new(0u32, |x| Some(if *x == 4 { 2 } else { x + 1 }));
+        let expected = Outcome::Cycle { start: 2, length: 3, state: 2 };
+        assert_eq!(expected, sim.floyd());
+        assert_eq!(expected, sim.brent());
+        assert_eq!(expected, sim.hashed());
+        assert_eq!(4, sim.state_at(1_000_000_000, &expected));
+    }
+
+    #[test]
+    fn halting() {
+        let mut sim = Simulator::new(0u32, |x| if *x < 10 { Some(x + 1) } else { None });
+        let expected = Outcome::Halted { steps: 10, state: 10 };
+        assert_eq!(expected, sim.floyd());
+        assert_eq!(expected, sim.brent());
+        assert_eq!(expected, sim.hashed());
+        assert_eq!(7, sim.state_at(7, &expected));
+        assert_eq!(10, sim.state_at(100, &expected));
+    }
+
+    proptest! {
+        #[test]
+        fn check_methods_agree(transitions in transitions()) {
+            let mut sim = Simulator::new(0usize, |x| transitions[*x]);
+            let outcome = sim.hashed();
+            assert_eq!(outcome, sim.floyd());
+            assert_eq!(outcome, sim.brent());
+            for n in 0..(3 * transitions.len()) {
+                assert_eq!(naive_state_at(&transitions, n), sim.state_at(n, &outcome));
+            }
+        }
+    }
+}
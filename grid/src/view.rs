@@ -0,0 +1,274 @@
+use core::{fmt::Display, ops::Index};
+
+use crate::{Direction, Grid, ParseError, Position};
+
+/// Read-only access to a rectangular grid of cells, shared by [`Grid`] and the borrowed grid types so that
+/// searches and scans can be written once for all of them.
+pub trait GridRead {
+    type Cell;
+
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    fn get(&self, pos: &Position) -> Option<&Self::Cell>;
+
+    fn in_bounds(&self, pos: &Position) -> bool {
+        pos.0 < self.width() && pos.1 < self.height()
+    }
+
+    /// Every position in the grid, in row-major order.
+    fn positions(&self) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width(), self.height());
+        (0..height).flat_map(move |y| (0..width).map(move |x| Position(x, y)))
+    }
+
+    /// The orthogonally adjacent positions that lie within the grid.
+    fn neighbors(&self, pos: &Position) -> impl Iterator<Item = Position> + use<'_, Self> {
+        let pos = pos.clone();
+        Direction::ALL.iter()
+            .filter_map(move |dir| &pos + &dir.to_offset())
+            .filter(|next| self.in_bounds(next))
+    }
+
+    /// Every cell with its position, in row-major order.
+    fn indexed_iter(&self) -> impl Iterator<Item = (Position, &Self::Cell)> {
+        self.positions().map(|pos| {
+            let cell = self.get(&pos).expect("positions are in bounds");
+            (pos, cell)
+        })
+    }
+}
+
+/// Mutable access to the cells of a grid, alongside [`GridRead`].
+pub trait GridWrite: GridRead {
+    fn get_mut(&mut self, pos: &Position) -> Option<&mut Self::Cell>;
+
+    /// Replaces the cell at `pos`, returning the old value, or `None` without writing anything if `pos` is out of bounds.
+    fn set(&mut self, pos: &Position, value: Self::Cell) -> Option<Self::Cell> {
+        self.get_mut(pos).map(|cell| core::mem::replace(cell, value))
+    }
+}
+
+impl <T> GridRead for Grid<T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, pos: &Position) -> Option<&T> {
+        Grid::get(self, pos)
+    }
+
+    fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        Grid::indexed_iter(self)
+    }
+}
+
+impl <T> GridWrite for Grid<T> {
+    fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        Grid::get_mut(self, pos)
+    }
+}
+
+impl <T> Grid<T> {
+    /// Every cell with its position, in row-major order.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.data.iter().enumerate().map(move |(i, cell)| (Position(i % width, i / width), cell))
+    }
+
+    /// Borrows the grid as a [`GridRef`].
+    pub fn view(&self) -> GridRef<'_, T> {
+        GridRef::new(&self.data, self.width, self.height, self.width)
+    }
+}
+
+/// A read-only grid borrowed from a slice, where each row starts `stride` cells after the previous one.
+///
+/// With a stride of `width + 1` this can wrap puzzle input directly, skipping over the newline at the end of each line.
+#[derive(Debug)]
+pub struct GridRef<'a, T = u8> {
+    data: &'a [T],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl <T> Clone for GridRef<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <T> Copy for GridRef<'_, T> {}
+
+impl <'a, T> GridRef<'a, T> {
+    /// Panics if `stride` is less than `width` or `data` is too short to hold every row.
+    pub fn new(data: &'a [T], width: usize, height: usize, stride: usize) -> Self {
+        assert!(stride >= width, "Stride {stride} is less than width {width}");
+        if height > 0 {
+            assert!(data.len() >= (height - 1) * stride + width, "Data is too short for a {width}x{height} grid with stride {stride}");
+        }
+        GridRef { data, width, height, stride }
+    }
+
+    fn index_of(&self, pos: &Position) -> usize {
+        if !self.in_bounds(pos) {
+            panic!("Out of bounds index: {pos}");
+        }
+        pos.0 + pos.1 * self.stride
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: &Position) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    /// Unlike [`Grid::get`], the returned reference borrows from the underlying slice rather than from the view.
+    pub fn get(&self, pos: &Position) -> Option<&'a T> {
+        if self.in_bounds(pos) {
+            Some(&self.data[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    /// The orthogonally adjacent positions that lie within the grid, as [`GridRead::neighbors`].
+    pub fn neighbors(&self, pos: &Position) -> impl Iterator<Item = Position> + '_ {
+        GridRead::neighbors(self, pos)
+    }
+
+    /// The rows of the grid, top to bottom, without any padding between them.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (data, width, stride) = (self.data, self.width, self.stride);
+        (0..self.height).map(move |y| &data[y * stride..y * stride + width])
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &'a T)> {
+        self.rows().enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, cell)| (Position(x, y), cell)))
+    }
+
+    /// Copies the cells into an owned [`Grid`].
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let data = self.rows().flat_map(|row| row.iter().cloned()).collect();
+        Grid::new(data, self.width, self.height)
+    }
+}
+
+impl <'a> GridRef<'a, u8> {
+    /// Wraps newline-separated text without copying it. The width is taken from the first line, and every other line must match it.
+    /// The final newline is optional.
+    pub fn from_input(input: &'a [u8]) -> Result<Self, ParseError> {
+        let width = input.iter().position(|&b| b == b'\n').unwrap_or(input.len());
+        let mut height = 0;
+        for (row, line) in input.split_inclusive(|&b| b == b'\n').enumerate() {
+            let found = line.strip_suffix(b"\n").unwrap_or(line).len();
+            if found != width {
+                return Err(ParseError::RaggedRow { row, expected: width, found });
+            }
+            height += 1;
+        }
+        Ok(GridRef::new(input, width, height, width + 1))
+    }
+}
+
+impl <T> GridRead for GridRef<'_, T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, pos: &Position) -> Option<&T> {
+        GridRef::get(self, pos)
+    }
+}
+
+impl <'a, T> From<&'a Grid<T>> for GridRef<'a, T> {
+    fn from(grid: &'a Grid<T>) -> Self {
+        grid.view()
+    }
+}
+
+impl <T> Index<&Position> for GridRef<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: &Position) -> &Self::Output {
+        &self.data[self.index_of(pos)]
+    }
+}
+
+impl <T: Display> Display for GridRef<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            if y < self.height - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_view_matches_grid(grid: Grid<u8>) {
+            let view = grid.view();
+            assert_eq!(grid, view.to_grid());
+            assert!(grid.indexed_iter().eq(view.indexed_iter()));
+            assert!(GridRead::positions(&grid).all(|pos| grid[&pos] == view[&pos]));
+        }
+    }
+
+    #[test]
+    fn input_view() {
+        let input = b"ab1\ncd2\nef3\n";
+        let grid = GridRef::from_input(input).unwrap();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(b'd', grid[&Position::new(1,1)]);
+        assert_eq!(None, grid.get(&Position::new(3,0)));
+        assert_eq!(vec![&b"ab1"[..], b"cd2", b"ef3"], grid.rows().collect::<Vec<_>>());
+        assert_eq!(vec![Position::new(1,0), Position::new(2,1), Position::new(1,2), Position::new(0,1)],
+            grid.neighbors(&Position::new(1,1)).collect::<Vec<_>>());
+        assert_eq!(2, grid.neighbors(&Position::new(0,0)).count());
+        let cells: Vec<u8> = grid.indexed_iter().map(|(_, c)| *c).collect();
+        assert_eq!(b"ab1cd2ef3".to_vec(), cells);
+    }
+
+    #[test]
+    fn input_without_final_newline() {
+        let grid = GridRef::from_input(b"ab\ncd").unwrap();
+        assert_eq!(Grid::new(b"abcd".to_vec(), 2, 2), grid.to_grid());
+        assert_eq!(ParseError::RaggedRow { row: 1, expected: 2, found: 1 }, GridRef::from_input(b"ab\nc\nde\n").unwrap_err());
+        assert_eq!(0, GridRef::from_input(b"").unwrap().height());
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{Grid, Offset, Position};
+
+/// Groups offsets by their primitive direction (see [`Offset::primitive`]), so that offsets along the same lattice line from a point end up together.
+/// Each group is sorted from nearest to farthest. Zero offsets are grouped under the zero vector.
+pub fn group_by_direction<I>(offsets: I) -> BTreeMap<Offset, Vec<Offset>>
+where
+    I: IntoIterator<Item = Offset>,
+{
+    let mut r: BTreeMap<Offset, Vec<Offset>> = BTreeMap::new();
+    for offset in offsets {
+        r.entry(offset.primitive()).or_default().push(offset);
+    }
+    for group in r.values_mut() {
+        group.sort_by_key(|offset| offset.x().unsigned_abs() + offset.y().unsigned_abs());
+    }
+    r
+}
+
+impl <T> Grid<T> {
+    /// Every primitive direction that can lead from one cell of this grid to another, in sorted order.
+    pub fn primitive_directions(&self) -> Vec<Offset> {
+        let (w, h) = (self.width as isize, self.height as isize);
+        let mut r = Vec::new();
+        for y in (1 - h)..h {
+            for x in (1 - w)..w {
+                let offset = Offset::new(x,y);
+                if offset.is_primitive() {
+                    r.push(offset);
+                }
+            }
+        }
+        r
+    }
+
+    /// Cells visited by repeatedly stepping `dir` from `from`, excluding `from` itself, until leaving the grid.
+    fn ray(&self, from: &Position, dir: Offset) -> impl Iterator<Item = Position> + '_ {
+        let mut pos = from.clone();
+        core::iter::from_fn(move || {
+            pos = (&pos + &dir).filter(|next| self.in_bounds(next))?;
+            Some(pos.clone())
+        })
+    }
+
+    /// For each primitive direction from `from`, the nearest cell for which `blocks` returns true. Directions that reach the edge of the grid unobstructed are omitted.
+    pub fn first_blocking<F>(&self, from: &Position, mut blocks: F) -> BTreeMap<Offset, Position>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.primitive_directions().into_iter()
+            .filter_map(|dir| {
+                let pos = self.ray(from, dir.clone()).find(|pos| blocks(&self[pos]))?;
+                Some((dir, pos))
+            })
+            .collect()
+    }
+
+    /// True if no cell strictly between `a` and `b` on the lattice line joining them is blocked. The endpoints themselves are not checked.
+    pub fn line_of_sight<F>(&self, a: &Position, b: &Position, mut blocks: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let dir = (b - a).primitive();
+        if dir.is_zero() {
+            return true;
+        }
+        self.ray(a, dir)
+            .take_while(|pos| pos != b)
+            .all(|pos| !blocks(&self[&pos]))
+    }
+
+    /// Marks every cell visible from `from`: those with [line of sight](Self::line_of_sight) to it, including the blocking cells themselves.
+    pub fn visible_from<F>(&self, from: &Position, mut blocks: F) -> Grid<bool>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut visible = self.map(|_| false);
+        visible[from] = true;
+        for dir in self.primitive_directions() {
+            for pos in self.ray(from, dir) {
+                visible[&pos] = true;
+                if blocks(&self[&pos]) {
+                    break;
+                }
+            }
+        }
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_primitive(x in -1000isize..1000, y in -1000isize..1000, scale in 1isize..100) {
+            let offset = Offset::new(x,y);
+            let primitive = offset.primitive();
+            assert_eq!(primitive, (&offset * scale).primitive());
+            assert_eq!(offset.is_zero(), primitive.is_zero());
+            assert_eq!(!offset.is_zero(), primitive.is_primitive());
+        }
+
+        #[test]
+        fn check_visibility(grid: Grid<bool>, x in 0usize..100, y in 0usize..100) {
+            let from = Position::new(x % grid.width(), y % grid.height());
+            let visible = grid.visible_from(&from, |b| *b);
+            for y in 0..grid.height() {
+                for x in 0..grid.width() {
+                    let pos = Position::new(x,y);
+                    assert_eq!(visible[&pos], grid.line_of_sight(&from, &pos, |b| *b), "{pos}");
+                }
+            }
+            for (dir, pos) in grid.first_blocking(&from, |b| *b) {
+                assert!(grid[&pos]);
+                assert!(visible[&pos]);
+                assert_eq!(dir, (&pos - &from).primitive());
+            }
+        }
+    }
+
+    #[test]
+    fn blocked_line() {
+        let grid = Grid::new(b"a.#.b".to_vec(), 5, 1);
+        let blocks = |c: &u8| *c == b'#';
+        assert!(!grid.line_of_sight(&Position::new(0,0), &Position::new(4,0), blocks));
+        assert!(grid.line_of_sight(&Position::new(0,0), &Position::new(2,0), blocks));
+        let blockers = grid.first_blocking(&Position::new(4,0), blocks);
+        assert_eq!(Some(&Position::new(2,0)), blockers.get(&Offset::new(-1,0)));
+        assert_eq!(1, blockers.len());
+    }
+
+    #[test]
+    fn off_lattice_cells_are_visible() {
+        // (2,1) is not on the lattice line through the blocker at (1,1)
+        let grid = Grid::new(b"....#.".to_vec(), 3, 2);
+        let visible = grid.visible_from(&Position::new(0,0), |c| *c == b'#');
+        assert!(visible[&Position::new(2,1)]);
+        assert!(visible[&Position::new(1,1)]);
+        assert!(grid.line_of_sight(&Position::new(0,0), &Position::new(2,0), |c| *c == b'#'));
+    }
+
+    #[test]
+    fn groups() {
+        let groups = group_by_direction([Offset::new(4,2), Offset::new(2,1), Offset::new(-2,-1), Offset::new(0,3)]);
+        assert_eq!(3, groups.len());
+        assert_eq!(vec![Offset::new(2,1), Offset::new(4,2)], groups[&Offset::new(2,1)]);
+        assert_eq!(vec![Offset::new(-2,-1)], groups[&Offset::new(-2,-1)]);
+        assert_eq!(vec![Offset::new(0,3)], groups[&Offset::new(0,1)]);
+    }
+}
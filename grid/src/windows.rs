@@ -0,0 +1,190 @@
+use core::ops::Index;
+
+use crate::{Grid, GridRead, Offset, Position};
+
+/// A borrowed rectangular view into a grid, addressed by positions relative to its top-left corner.
+///
+/// Padded windows may hang over the edges of the grid, in which case the cells outside it read as the fill value.
+#[derive(Debug)]
+pub struct Window<'a, T> {
+    grid: &'a Grid<T>,
+    origin: (isize, isize),
+    width: usize,
+    height: usize,
+    fill: Option<&'a T>,
+}
+
+impl <T> Clone for Window<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <T> Copy for Window<'_, T> {}
+
+impl <'a, T> Window<'a, T> {
+    /// The grid position of the window's top-left corner, which may be negative for padded windows.
+    pub fn origin(&self) -> Offset {
+        Offset::new(self.origin.0, self.origin.1)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: &Position) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    /// The grid position under `pos`, or `None` if `pos` is outside the window or falls off the edge of the grid.
+    pub fn grid_position(&self, pos: &Position) -> Option<Position> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        let x = self.origin.0.checked_add_unsigned(pos.0)?;
+        let y = self.origin.1.checked_add_unsigned(pos.1)?;
+        let pos = Position(x.try_into().ok()?, y.try_into().ok()?);
+        self.grid.in_bounds(&pos).then_some(pos)
+    }
+
+    /// The cell at `pos` relative to the window, or the fill value if it is off the edge of the grid.
+    /// Returns `None` if `pos` is outside the window.
+    pub fn get(&self, pos: &Position) -> Option<&'a T> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        match self.grid_position(pos) {
+            Some(pos) => self.grid.get(&pos),
+            None => self.fill,
+        }
+    }
+
+    /// Every cell in the window with its position relative to the window, in row-major order.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Position(x, y)))
+            .map(|pos| {
+                let cell = self.get(&pos).expect("positions within the window have a value");
+                (pos, cell)
+            })
+    }
+}
+
+impl <T> GridRead for Window<'_, T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, pos: &Position) -> Option<&T> {
+        Window::get(self, pos)
+    }
+}
+
+impl <T> Index<&Position> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: &Position) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("Out of bounds index: {pos}"))
+    }
+}
+
+/// Iterator over the windows of a grid, returned by [`Grid::windows`] and [`Grid::padded_windows`].
+#[derive(Debug)]
+pub struct Windows<'a, T> {
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    fill: Option<&'a T>,
+    /// The range of origins along each axis, inclusive, with the next origin to yield.
+    xs: (isize, isize),
+    ys: (isize, isize),
+    next: (isize, isize),
+}
+
+impl <'a, T> Iterator for Windows<'a, T> {
+    type Item = Window<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.next;
+        if y > self.ys.1 || self.xs.0 > self.xs.1 {
+            return None;
+        }
+        self.next = if x < self.xs.1 { (x + 1, y) } else { (self.xs.0, y + 1) };
+        Some(Window { grid: self.grid, origin: (x, y), width: self.width, height: self.height, fill: self.fill })
+    }
+}
+
+impl <T> Grid<T> {
+    fn windows_from<'a>(&'a self, width: usize, height: usize, fill: Option<&'a T>, xs: (isize, isize), ys: (isize, isize)) -> Windows<'a, T> {
+        assert!(width > 0 && height > 0, "Window dimensions must be non-zero");
+        Windows { grid: self, width, height, fill, xs, ys, next: (xs.0, ys.0) }
+    }
+
+    /// Every `width` x `height` view that lies entirely within the grid, in row-major order of their top-left corners.
+    /// Yields nothing if the window is larger than the grid. Panics if either dimension is zero.
+    pub fn windows(&self, width: usize, height: usize) -> Windows<'_, T> {
+        let last = |size: usize, window: usize| size as isize - window as isize;
+        self.windows_from(width, height, None, (0, last(self.width, width)), (0, last(self.height, height)))
+    }
+
+    /// Every `width` x `height` view that overlaps the grid at all, including partial ones at the edges, in which cells beyond the
+    /// grid read as `fill`. Panics if either dimension is zero.
+    pub fn padded_windows<'a>(&'a self, width: usize, height: usize, fill: &'a T) -> Windows<'a, T> {
+        let first = |window: usize| 1 - window as isize;
+        let last = |size: usize| size as isize - 1;
+        self.windows_from(width, height, Some(fill), (first(width), last(self.width)), (first(height), last(self.height)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn check_window_counts(grid: Grid<u8>, width in 1usize..5, height in 1usize..5) {
+            let full = grid.width().saturating_sub(width - 1) * grid.height().saturating_sub(height - 1);
+            assert_eq!(full, grid.windows(width, height).count());
+            let padded = (grid.width() + width - 1) * (grid.height() + height - 1);
+            assert_eq!(padded, grid.padded_windows(width, height, &0).count());
+            for window in grid.windows(width, height) {
+                assert!(window.indexed_iter().all(|(pos, cell)| grid[&window.grid_position(&pos).unwrap()] == *cell));
+            }
+        }
+    }
+
+    #[test]
+    fn cross_pattern() {
+        let grid: Grid<u8> = ["M.S", ".A.", "M.S", ".A."].iter().map(|row| row.bytes()).collect();
+        let crosses: Vec<Offset> = grid.windows(3, 3)
+            .filter(|w| w[&Position::new(1,1)] == b'A' && w[&Position::new(0,0)] == b'M' && w[&Position::new(2,2)] == b'S')
+            .map(|w| w.origin())
+            .collect();
+        assert_eq!(vec![Offset::new(0,0)], crosses);
+        assert_eq!(0, grid.windows(4, 1).count());
+    }
+
+    #[test]
+    fn padded() {
+        let grid = Grid::new(vec![1, 2, 3, 4], 2, 2);
+        let windows: Vec<Window<i32>> = grid.padded_windows(2, 2, &0).collect();
+        assert_eq!(9, windows.len());
+        let first = windows[0];
+        assert_eq!(Offset::new(-1,-1), first.origin());
+        assert_eq!(vec![0, 0, 0, 1], first.indexed_iter().map(|(_, c)| *c).collect::<Vec<_>>());
+        assert_eq!(None, first.get(&Position::new(2,0)));
+        let sums: Vec<i32> = windows.iter().map(|w| w.indexed_iter().map(|(_, c)| c).sum()).collect();
+        assert_eq!(vec![1, 3, 2, 4, 10, 6, 3, 7, 4], sums);
+    }
+}
@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use grid::{Grid, GridWrite, Position};
+
+const MAX_ZOOM: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(isize, isize),
+    /// Moves by this many screens vertically.
+    Page(isize),
+    ZoomIn,
+    ZoomOut,
+    ToggleHighlight,
+    /// Takes the value under the cursor as the brush.
+    PickBrush,
+    /// Sets the brush to a specific value.
+    SetBrush(u8),
+    Paint,
+}
+
+/// The state of the explorer, independent of the terminal.
+#[derive(Debug, Clone)]
+pub struct Explorer {
+    grid: Grid<u8>,
+    cursor: (usize, usize),
+    /// The top-left cell on screen.
+    scroll: (usize, usize),
+    /// The screen area available for cells, in terminal columns and rows.
+    view: (usize, usize),
+    zoom: usize,
+    highlight: bool,
+    brush: u8,
+    modified: bool,
+}
+
+impl Explorer {
+    /// Panics if the grid is empty, since there is nowhere to put the cursor.
+    pub fn new(grid: Grid<u8>) -> Self {
+        assert!(grid.width() > 0 && grid.height() > 0, "Cannot explore an empty grid");
+        Explorer { grid, cursor: (0, 0), scroll: (0, 0), view: (80, 24), zoom: 1, highlight: false, brush: b'#', modified: false }
+    }
+
+    pub fn grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    pub fn cursor(&self) -> Position {
+        Position::new(self.cursor.0, self.cursor.1)
+    }
+
+    pub fn brush(&self) -> u8 {
+        self.brush
+    }
+
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.modified = false;
+    }
+
+    /// Terminal columns used to draw one cell.
+    pub fn cell_width(&self) -> usize {
+        self.zoom
+    }
+
+    /// Terminal rows used to draw one cell. Terminal characters are roughly twice as tall as they are wide, so this grows at half the rate.
+    pub fn cell_height(&self) -> usize {
+        self.zoom.div_ceil(2)
+    }
+
+    pub fn resize(&mut self, columns: usize, rows: usize) {
+        self.view = (columns, rows);
+        self.scroll_to_cursor();
+    }
+
+    /// The number of cells that fit on screen across and down.
+    fn visible_cells(&self) -> (usize, usize) {
+        ((self.view.0 / self.cell_width()).max(1), (self.view.1 / self.cell_height()).max(1))
+    }
+
+    /// The columns and rows of the grid currently on screen.
+    pub fn visible(&self) -> (Range<usize>, Range<usize>) {
+        let (across, down) = self.visible_cells();
+        (
+            self.scroll.0..self.grid.width().min(self.scroll.0 + across),
+            self.scroll.1..self.grid.height().min(self.scroll.1 + down),
+        )
+    }
+
+    /// True if `pos` should be highlighted as having the same value as the cell under the cursor.
+    pub fn is_highlighted(&self, pos: &Position) -> bool {
+        self.highlight && self.grid.get(pos) == self.grid.get(&self.cursor())
+    }
+
+    pub fn apply(&mut self, action: Action) {
+        match action {
+            Action::Move(dx, dy) => self.move_cursor(dx, dy),
+            Action::Page(pages) => self.move_cursor(0, pages * self.visible_cells().1 as isize),
+            Action::ZoomIn => self.zoom = (self.zoom + 1).min(MAX_ZOOM),
+            Action::ZoomOut => self.zoom = (self.zoom - 1).max(1),
+            Action::ToggleHighlight => self.highlight = !self.highlight,
+            Action::PickBrush => self.brush = self.grid[&self.cursor()],
+            Action::SetBrush(brush) => self.brush = brush,
+            Action::Paint => {
+                let cursor = self.cursor();
+                if self.grid.set(&cursor, self.brush) != Some(self.brush) {
+                    self.modified = true;
+                }
+            },
+        }
+        self.scroll_to_cursor();
+    }
+
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (x, y) = self.cursor;
+        self.cursor = (
+            x.saturating_add_signed(dx).min(self.grid.width() - 1),
+            y.saturating_add_signed(dy).min(self.grid.height() - 1),
+        );
+    }
+
+    /// Scrolls the least distance that brings the cursor on screen.
+    fn scroll_to_cursor(&mut self) {
+        let (across, down) = self.visible_cells();
+        let scroll_axis = |scroll: usize, cursor: usize, size: usize| scroll.clamp((cursor + 1).saturating_sub(size), cursor);
+        self.scroll = (scroll_axis(self.scroll.0, self.cursor.0, across), scroll_axis(self.scroll.1, self.cursor.1, down));
+    }
+
+    /// The grid as text, one line per row.
+    pub fn to_text(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for row in self.grid.view().rows() {
+            out.extend_from_slice(row);
+            out.push(b'\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::GridRead;
+
+    fn sample() -> Explorer {
+        let grid: Grid<u8> = ["..#...", "#.....", "....#."].iter().map(|row| row.bytes()).collect();
+        Explorer::new(grid)
+    }
+
+    #[test]
+    fn scrolling() {
+        let mut explorer = sample();
+        explorer.resize(4, 2);
+        assert_eq!((0..4, 0..2), explorer.visible());
+        explorer.apply(Action::Move(10, 10));
+        assert_eq!(Position::new(5,2), explorer.cursor());
+        assert_eq!((2..6, 1..3), explorer.visible());
+        explorer.apply(Action::ZoomIn);
+        assert_eq!((4..6, 1..3), explorer.visible());
+        explorer.apply(Action::Page(-1));
+        assert_eq!(Position::new(5,0), explorer.cursor());
+        assert_eq!((4..6, 0..2), explorer.visible());
+    }
+
+    #[test]
+    fn paint_and_highlight() {
+        let mut explorer = sample();
+        explorer.apply(Action::Move(2, 0));
+        explorer.apply(Action::ToggleHighlight);
+        let highlighted: Vec<Position> = explorer.grid().positions().filter(|pos| explorer.is_highlighted(pos)).collect();
+        assert_eq!(vec![Position::new(2,0), Position::new(0,1), Position::new(4,2)], highlighted);
+
+        explorer.apply(Action::Paint);
+        assert!(!explorer.is_modified());
+        explorer.apply(Action::SetBrush(b'O'));
+        explorer.apply(Action::Paint);
+        assert!(explorer.is_modified());
+        assert_eq!(b"..O...\n#.....\n....#.\n".to_vec(), explorer.to_text());
+    }
+}
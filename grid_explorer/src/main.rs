@@ -0,0 +1,179 @@
+//! Interactive viewer and editor for text grids such as puzzle inputs.
+//!
+//! Usage: `grid_explorer <path> [--output <path>]`
+//!
+//! Arrow keys or hjkl move the cursor and PageUp/PageDown move a screen at a time; the view scrolls to follow the cursor.
+//! `+` and `-` zoom, `*` highlights every cell with the same value as the one under the cursor, `y` picks up the value under the cursor
+//! as the brush, typing any other printable character makes it the brush, and space paints the brush at the cursor.
+//! `s` saves to the output path, which defaults to the input, and `q` or Esc quits. Quitting with unsaved changes asks for a second press.
+
+use std::{fs::File, io::{self, BufRead, BufReader, Write}, panic};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use grid::{Grid, GridBuilder, Position};
+
+mod explorer;
+
+use explorer::{Action, Explorer};
+
+enum Command {
+    Apply(Action),
+    Save,
+    Quit,
+}
+
+fn key_command(key: KeyEvent) -> Option<Command> {
+    let command = match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Command::Quit,
+        KeyCode::Left | KeyCode::Char('h') => Command::Apply(Action::Move(-1, 0)),
+        KeyCode::Right | KeyCode::Char('l') => Command::Apply(Action::Move(1, 0)),
+        KeyCode::Up | KeyCode::Char('k') => Command::Apply(Action::Move(0, -1)),
+        KeyCode::Down | KeyCode::Char('j') => Command::Apply(Action::Move(0, 1)),
+        KeyCode::PageUp => Command::Apply(Action::Page(-1)),
+        KeyCode::PageDown => Command::Apply(Action::Page(1)),
+        KeyCode::Char('+') | KeyCode::Char('=') => Command::Apply(Action::ZoomIn),
+        KeyCode::Char('-') => Command::Apply(Action::ZoomOut),
+        KeyCode::Char('*') => Command::Apply(Action::ToggleHighlight),
+        KeyCode::Char('y') => Command::Apply(Action::PickBrush),
+        KeyCode::Char(' ') => Command::Apply(Action::Paint),
+        KeyCode::Char('s') => Command::Save,
+        KeyCode::Char('q') | KeyCode::Esc => Command::Quit,
+        KeyCode::Char(c) if c.is_ascii_graphic() => Command::Apply(Action::SetBrush(c as u8)),
+        _ => return None,
+    };
+    Some(command)
+}
+
+fn draw<W: Write>(out: &mut W, explorer: &Explorer, message: &str) -> io::Result<()> {
+    queue!(out, Clear(ClearType::All))?;
+    let (xs, ys) = explorer.visible();
+    let (cell_width, cell_height) = (explorer.cell_width(), explorer.cell_height());
+    let cursor = explorer.cursor();
+    for (row, y) in ys.clone().enumerate() {
+        for line in 0..cell_height {
+            queue!(out, MoveTo(0, (row * cell_height + line) as u16))?;
+            for x in xs.clone() {
+                let pos = Position::new(x,y);
+                let text = (explorer.grid()[&pos] as char).to_string().repeat(cell_width);
+                let styled = if pos == cursor {
+                    text.reverse()
+                } else if explorer.is_highlighted(&pos) {
+                    text.black().on_yellow()
+                } else {
+                    text.stylize()
+                };
+                queue!(out, PrintStyledContent(styled))?;
+            }
+        }
+    }
+
+    let status = format!("{cursor} = '{}'  brush '{}'  zoom {}{}  {message}",
+        explorer.grid()[&cursor] as char,
+        explorer.brush() as char,
+        explorer.zoom(),
+        if explorer.is_modified() { "  [modified]" } else { "" });
+    queue!(out, MoveTo(0, (ys.len() * cell_height) as u16), Print(status.reverse()))?;
+    out.flush()
+}
+
+fn run<W: Write>(out: &mut W, explorer: &mut Explorer, output: &str) -> io::Result<()> {
+    let mut message = String::new();
+    // Set after a quit with unsaved changes, so that quitting again right away discards them
+    let mut confirm_quit = false;
+    loop {
+        let (columns, rows) = terminal::size()?;
+        // Leave the last row for the status line
+        explorer.resize(columns as usize, rows.saturating_sub(1) as usize);
+        draw(out, explorer, &message)?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        message.clear();
+        let confirmed = std::mem::take(&mut confirm_quit);
+        match key_command(key) {
+            Some(Command::Apply(action)) => explorer.apply(action),
+            Some(Command::Save) => {
+                message = match File::create(output).and_then(|mut file| file.write_all(&explorer.to_text())) {
+                    Ok(()) => {
+                        explorer.mark_saved();
+                        format!("saved to {output}")
+                    },
+                    Err(e) => format!("save failed: {e}"),
+                };
+            },
+            Some(Command::Quit) if explorer.is_modified() && !confirmed => {
+                confirm_quit = true;
+                message = "unsaved changes: press q again to discard them, or s to save".into();
+            },
+            Some(Command::Quit) => return Ok(()),
+            None => (),
+        }
+    }
+}
+
+/// Puts the terminal in raw mode on the alternate screen, and restores it when dropped, including while unwinding from a panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        // Restore the terminal before a panic message is printed, or it would be lost along with the alternate screen
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            hook(info);
+        }));
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    // Nothing more can be done about a failure while exiting
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+fn read_data(filename: &str) -> io::Result<Grid<u8>> {
+    let mut builder = GridBuilder::new();
+    for line in BufReader::new(File::open(filename)?).lines() {
+        builder.push_row(line?.into_bytes()).map_err(io::Error::other)?;
+    }
+    let grid = builder.build();
+    if grid.width() == 0 || grid.height() == 0 {
+        return Err(io::Error::other("Expected non-empty grid"));
+    }
+    Ok(grid)
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (input, output) = match &args[..] {
+        [input] => (input, input),
+        [input, flag, output] if flag == "--output" => (input, output),
+        _ => {
+            eprintln!("Usage: grid_explorer <path> [--output <path>]");
+            std::process::exit(2);
+        },
+    };
+    let mut explorer = Explorer::new(read_data(input)?);
+
+    let _terminal = TerminalGuard::enter()?;
+    run(&mut io::stdout(), &mut explorer, output)
+}